@@ -22,4 +22,37 @@ pub const UPLOAD_TIER_2_REWARD: u32 = 35; // 50 - 70
 pub const UPLOAD_TIER_3_REWARD: u32 = 300; // >= 71
 
 // Token Redemption
-pub const POINTS_TO_ARB_RATE: u64 = 1; // 1 Point = 1 ARB (Adjust decimals as needed)
+// Whole ARB paid per point, as numerator / denominator; scaled by the mint's decimals at redemption
+pub const POINTS_TO_ARB_NUMERATOR: u64 = 1; // 1 Point = 1 ARB
+pub const POINTS_TO_ARB_DENOMINATOR: u64 = 1;
+pub const MIN_REDEEM_POINTS: u64 = 10;
+pub const MAX_REDEEM_POINTS: u64 = 100_000;
+pub const VESTING_CLIFF: i64 = 30 * 86_400;
+pub const VESTING_DURATION: i64 = 180 * 86_400;
+pub const MAX_VESTING_DURATION: i64 = 4 * SECONDS_PER_YEAR;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const DECAY_PERIOD: i64 = 90 * 86_400;
+pub const DECAY_RATE_BPS: u16 = 1_000; // 10% of the standing score per idle period
+pub const MIN_DECAY_PERIOD: i64 = 86_400;
+pub const MAX_DECAY_PERIOD: i64 = 5 * SECONDS_PER_YEAR;
+
+// Contributor tiers: minimum standing score for each tier above Novice
+pub const CONTRIBUTOR_TIER_THRESHOLD: u64 = 100;
+pub const TRUSTED_TIER_THRESHOLD: u64 = 1_000;
+pub const CURATOR_TIER_THRESHOLD: u64 = 10_000;
+pub const MAX_FILE_SIZE: u64 = 104_857_600; // 100MB
+pub const TRUSTED_UPLOAD_SIZE: u64 = 52_428_800; // Larger uploads need the Trusted tier
+
+// Publication references stored on a Citation
+pub const MAX_PUBLICATION_IDENTIFIER_LEN: usize = 256;
+pub const MAX_PUBLICATION_VENUE_LEN: usize = 128;
+pub const MIN_PUBLICATION_YEAR: u16 = 1900;
+pub const SECONDS_PER_YEAR: i64 = 31_556_952; // Average Gregorian year
+
+// Review staking
+pub const MIN_REVIEW_STAKE: u64 = 100_000_000_000; // 100 ARB at 9 decimals
+pub const REVIEW_UNBONDING_PERIOD: i64 = 7 * 86_400;
+pub const MAX_REVIEW_UNBONDING_PERIOD: i64 = 90 * 86_400;
+
+// Upper bound for any single configurable reward or weight
+pub const MAX_POINT_REWARD: u32 = 10_000;
@@ -28,8 +28,80 @@ pub enum ErrorCode {
     InvalidReputationUpdate,
     #[msg("Unauthorized reputation update")]
     UnauthorizedReputationUpdate,
-    #[msg("Dataset with this content hash already exists for this contributor")]
+    #[msg("Dataset with this content hash already exists")]
     DuplicateDataset,
     #[msg("Invalid contributor status")]
     InvalidContributorStatus,
+    #[msg("Signer does not hold the required role")]
+    MissingRole,
+    #[msg("Signer is not the registry admin")]
+    UnauthorizedAdmin,
+    #[msg("Signer is not the pending registry admin")]
+    NotPendingAdmin,
+    #[msg("Account is not a legacy registry")]
+    InvalidLegacyRegistry,
+    #[msg("Config value out of bounds")]
+    InvalidConfig,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("Quality score attestation is missing or invalid")]
+    InvalidAttestation,
+    #[msg("Contributors cannot download their own dataset")]
+    SelfDownload,
+    #[msg("Contributors cannot cite their own dataset")]
+    SelfCitation,
+    #[msg("Publication reference is malformed or does not match its hash")]
+    InvalidPublication,
+    #[msg("Contributors cannot review their own dataset")]
+    SelfReview,
+    #[msg("Review rubric scores must be between 0 and 100")]
+    InvalidReview,
+    #[msg("Review reward has already been paid")]
+    ReviewAlreadyCredited,
+    #[msg("Dataset has no reviews to score")]
+    NoReviews,
+    #[msg("Token mint does not match the configured ARB mint")]
+    InvalidMint,
+    #[msg("Stake amount must be non-zero and within the available stake")]
+    InvalidStakeAmount,
+    #[msg("Reviewer has not staked enough ARB")]
+    InsufficientStake,
+    #[msg("Stake is still unbonding")]
+    StakeUnbonding,
+    #[msg("Review has already been slashed")]
+    ReviewAlreadySlashed,
+    #[msg("Reward vault has already been initialized")]
+    RewardVaultAlreadyInitialized,
+    #[msg("Token account is not the configured reward vault")]
+    InvalidRewardVault,
+    #[msg("Funding amount must be non-zero")]
+    InvalidFundingAmount,
+    #[msg("No redeemable points available")]
+    NothingToRedeem,
+    #[msg("Redemption is below the minimum claim")]
+    RedeemBelowMinimum,
+    #[msg("Redemption is above the maximum claim")]
+    RedeemAboveMaximum,
+    #[msg("Redemption exceeds the available points")]
+    RedeemExceedsBalance,
+    #[msg("Reward vault does not hold enough ARB")]
+    VaultInsufficientFunds,
+    #[msg("Redemptions must go through a vesting schedule")]
+    VestingRequired,
+    #[msg("Vesting is not enabled")]
+    VestingDisabled,
+    #[msg("No vested tokens to claim")]
+    NothingVested,
+    #[msg("Account is not in a known pre-migration layout")]
+    UnsupportedAccountLayout,
+    #[msg("Account is already on the current layout")]
+    AccountAlreadyMigrated,
+    #[msg("Contributor tier is too low for this action")]
+    InsufficientTier,
+    #[msg("Signer is not the program upgrade authority")]
+    UnauthorizedDeployer,
+    #[msg("Every superseded dataset version must be closed with the dataset")]
+    IncompleteVersionHistory,
+    #[msg("Stake is locked behind credited reviews")]
+    StakeLocked,
 }
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::{ContributorTier, ReviewVerdict, Role, ScoringParams};
+
 #[event]
 pub struct DatasetCreated {
     pub id: Pubkey,
@@ -9,12 +11,43 @@ pub struct DatasetCreated {
     pub upload_timestamp: i64,
 }
 
+#[event]
+pub struct DatasetUpdated {
+    pub id: Pubkey,
+    pub contributor: Pubkey,
+    pub version: u32,
+    pub content_hash: [u8; 32],
+    pub quality_score: u8,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct DatasetStatusChanged {
+    pub id: Pubkey,
+    pub is_active: bool,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct DatasetClosed {
+    pub id: Pubkey,
+    pub contributor: Pubkey,
+}
+
 #[event]
 pub struct ReputationUpdated {
     pub contributor: Pubkey,
     pub action: String,  // e.g., "upload", "download", "citation"
     pub new_dataset_count: u32,
-    pub new_reputation_score: u32,  // Can compute here if you add logic (e.g., quality * uploads)
+    pub new_reputation_score: u64,  // Can compute here if you add logic (e.g., quality * uploads)
+}
+
+#[event]
+pub struct DownloadRecorded {
+    pub dataset_id: Pubkey,
+    pub downloader: Pubkey,
+    pub contributor: Pubkey,
+    pub download_time: i64,
 }
 
 #[event]
@@ -22,5 +55,135 @@ pub struct CitationRecorded {  // Bonus: For citation tracking
     pub dataset_id: Pubkey,
     pub citer: Pubkey,
     pub contributor: Pubkey,
+    pub publication_hash: [u8; 32],
     pub citing_time: i64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct ReviewSubmitted {
+    pub dataset: Pubkey,
+    pub reviewer: Pubkey,
+    pub verdict: ReviewVerdict,
+    pub score: u8,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct QualityScoreRecalculated {
+    pub dataset: Pubkey,
+    pub previous_score: u8,
+    pub new_score: u8,
+    pub review_count: u32,
+    pub upload_points: u32,
+}
+
+#[event]
+pub struct ReviewStakeChanged {
+    pub reviewer: Pubkey,
+    pub amount: u64,
+    pub unbonding_amount: u64,
+    pub unbonding_until: i64,
+}
+
+#[event]
+pub struct ReviewSlashed {
+    pub review: Pubkey,
+    pub reviewer: Pubkey,
+    pub amount: u64,
+    pub slashed_by: Pubkey,
+}
+
+#[event]
+pub struct RewardVaultFunded {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub vault_balance: u64,
+}
+
+#[event]
+pub struct PointsRedeemed {
+    pub user: Pubkey,
+    pub points: u64,
+    pub token_amount: u64,
+    pub remaining_points: u64,
+}
+
+#[event]
+pub struct VestingScheduleCreated {
+    pub beneficiary: Pubkey,
+    pub schedule: Pubkey,
+    pub amount: u64,
+    pub locked_amount: u64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct VestedTokensClaimed {
+    pub beneficiary: Pubkey,
+    pub schedule: Pubkey,
+    pub amount: u64,
+    pub claimed_amount: u64,
+    pub locked_amount: u64,
+}
+
+#[event]
+pub struct TierChanged {
+    pub contributor: Pubkey,
+    pub previous_tier: ContributorTier,
+    pub new_tier: ContributorTier,
+    pub reputation_score: u64,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub previous_size: u32,
+    pub account_version: u8,
+}
+
+#[event]
+pub struct ReputationMigrated {
+    pub contributor: Pubkey,
+    pub reputation_score: u64,
+    pub redeemable_points: u64,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub holder: Pubkey,
+    pub role: Role,
+    pub granted_by: Pubkey,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub holder: Pubkey,
+    pub role: Role,
+    pub revoked_by: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub updated_by: Pubkey,
+    pub scoring: ScoringParams,
+}
+
+#[event]
+pub struct PauseStateChanged {
+    pub updated_by: Pubkey,
+    pub paused: bool,
+    pub paused_categories: u8,
+}
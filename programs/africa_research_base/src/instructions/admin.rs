@@ -0,0 +1,139 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::error::ErrorCode;
+use crate::events::{AdminTransferProposed, AdminTransferred};
+use crate::program::AfricaResearchBase;
+use crate::{LegacyRegistry, Registry};
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"registry"],
+        bump = registry.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"registry"],
+        bump = registry.bump,
+        constraint = registry.pending_admin == Some(new_admin.key()) @ ErrorCode::NotPendingAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateRegistry<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Pre-singleton registry; discriminator and stored admin are verified in the handler
+    #[account(
+        mut,
+        seeds = [b"registry", admin.key().as_ref()],
+        bump,
+        owner = crate::ID @ ErrorCode::InvalidLegacyRegistry
+    )]
+    pub legacy_registry: UncheckedAccount<'info>,
+
+    // Any wallet could seed a legacy registry, so the deployer has to vouch for this one
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ ErrorCode::UnauthorizedDeployer
+    )]
+    pub program: Program<'info, AfricaResearchBase>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::UnauthorizedDeployer
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Registry::INIT_SPACE,
+        seeds = [b"registry"],
+        bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ProposeAdmin<'info> {
+    pub fn propose_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        self.registry.pending_admin = Some(new_admin);
+
+        emit!(AdminTransferProposed {
+            admin: self.admin.key(),
+            pending_admin: new_admin,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn accept_admin(&mut self) -> Result<()> {
+        let registry = &mut self.registry;
+        let previous_admin = registry.admin;
+
+        registry.admin = self.new_admin.key();
+        registry.pending_admin = None;
+
+        emit!(AdminTransferred {
+            previous_admin,
+            new_admin: registry.admin,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> MigrateRegistry<'info> {
+    // Authority accounts are seeded by the registry key, so roles granted under the
+    // legacy registry have to be granted again once the singleton exists.
+    pub fn migrate_registry(&mut self, bumps: &MigrateRegistryBumps) -> Result<()> {
+        let legacy = {
+            let data = self.legacy_registry.try_borrow_data()?;
+            require!(
+                data.starts_with(Registry::DISCRIMINATOR),
+                ErrorCode::InvalidLegacyRegistry
+            );
+            LegacyRegistry::deserialize(&mut &data[8..])
+                .map_err(|_| ErrorCode::InvalidLegacyRegistry)?
+        };
+        require_keys_eq!(legacy.admin, self.admin.key(), ErrorCode::UnauthorizedAdmin);
+
+        self.registry.set_inner(Registry {
+            account_version: Registry::ACCOUNT_VERSION,
+            admin: legacy.admin,
+            pending_admin: None,
+            total_datasets: legacy.total_datasets,
+            total_downloads: legacy.total_downloads,
+            bump: bumps.registry,
+        });
+
+        // Close the legacy account and refund its rent to the admin
+        let legacy_info = self.legacy_registry.to_account_info();
+        let admin_info = self.admin.to_account_info();
+        let refund = legacy_info.lamports();
+        **admin_info.try_borrow_mut_lamports()? = admin_info
+            .lamports()
+            .checked_add(refund)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        **legacy_info.try_borrow_mut_lamports()? = 0;
+        legacy_info.assign(&system_program::ID);
+        legacy_info.resize(0)?;
+
+        Ok(())
+    }
+}
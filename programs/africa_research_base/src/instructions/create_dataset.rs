@@ -1,15 +1,34 @@
 #![allow(unexpected_cfgs)]
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked,
+};
 
-use crate::{Dataset, Registry, Reputation};
+use crate::constants::MAX_FILE_SIZE;
+use crate::{
+    AttestationNonce, Authority, ContentHashIndex, ContributorTier, Dataset, PauseCategory,
+    ProgramConfig, Registry, Reputation, Role,
+};
 use crate::events::{DatasetCreated, ReputationUpdated};
 use crate::error::ErrorCode;
+use crate::instructions::update_reputation::{
+    calculate_reputation_score, current_tier, rescore_upload_points, touch_activity,
+};
 
 #[derive(Accounts)]
+#[instruction(
+    content_hash: [u8; 32],
+    ai_metadata: Vec<u8>,
+    file_name: Vec<u8>,
+    file_size: u64,
+    data_uri: [u8; 256],
+    column_count: u64,
+    row_count: u64,
+    quality_score: u8,
+    nonce: u64
+)]
 pub struct CreateDataset <'info> {
-    #[account(mut)]
-    pub admin: Signer<'info>,
-
     #[account(mut)]
     pub user: Signer<'info>,
 
@@ -17,11 +36,18 @@ pub struct CreateDataset <'info> {
     pub contributor: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"registry", admin.key().as_ref()],
+        seeds = [b"registry"],
         bump = registry.bump
     )]
     pub registry: Account <'info, Registry>,
 
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(PauseCategory::Datasets) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
     
     #[account(
         init,
@@ -32,6 +58,15 @@ pub struct CreateDataset <'info> {
     )]
     pub dataset: Account <'info, Dataset>,
 
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = 8 + ContentHashIndex::INIT_SPACE,
+        seeds = [b"hash", content_hash.as_ref()],
+        bump
+    )]
+    pub hash_index: Account<'info, ContentHashIndex>,
+
     #[account(
         mut,
         seeds = [b"reputation", contributor.key().as_ref()],
@@ -39,11 +74,94 @@ pub struct CreateDataset <'info> {
     )]
     pub reputation: Account <'info, Reputation>,
 
+    /// CHECK: Key of the oracle that signed the quality attestation; its role is checked below
+    pub quality_oracle: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"authority", registry.key().as_ref(), quality_oracle.key().as_ref()],
+        bump = oracle_authority.bump,
+        constraint = oracle_authority.has_role(Role::QualityOracle) @ ErrorCode::MissingRole
+    )]
+    pub oracle_authority: Account<'info, Authority>,
+
+    #[account(
+        init,
+        payer = contributor,
+        space = 8 + AttestationNonce::INIT_SPACE,
+        seeds = [b"nonce", quality_oracle.key().as_ref(), &nonce.to_le_bytes()],
+        bump
+    )]
+    pub attestation_nonce: Account<'info, AttestationNonce>,
+
+    /// CHECK: Instructions sysvar, read to find the Ed25519 verification instruction
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
+// Bytes the quality oracle signs: contributor || content_hash || quality_score || row_count || column_count || file_size || nonce
+// The contributor is included so an attestation seen in flight cannot be claimed by another wallet
+pub fn quality_attestation_message(
+    contributor: &Pubkey,
+    content_hash: &[u8; 32],
+    quality_score: u8,
+    row_count: u64,
+    column_count: u64,
+    file_size: u64,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(97);
+    message.extend_from_slice(contributor.as_ref());
+    message.extend_from_slice(content_hash);
+    message.push(quality_score);
+    message.extend_from_slice(&row_count.to_le_bytes());
+    message.extend_from_slice(&column_count.to_le_bytes());
+    message.extend_from_slice(&file_size.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message
+}
+
+// Requires the instruction right before this one to be an Ed25519 program check of `message` signed by `oracle`.
+// The precompile itself rejects the transaction if the signature is invalid.
+pub(crate) fn verify_quality_attestation(instructions: &AccountInfo, oracle: &Pubkey, message: &[u8]) -> Result<()> {
+    let current_index = load_current_index_checked(instructions)? as usize;
+    require!(current_index > 0, ErrorCode::InvalidAttestation);
+
+    let ed25519_ix = load_instruction_at_checked(current_index - 1, instructions)?;
+    require_keys_eq!(ed25519_ix.program_id, ed25519_program::ID, ErrorCode::InvalidAttestation);
+
+    let data = &ed25519_ix.data;
+    require!(data.len() >= 16 && data[0] == 1, ErrorCode::InvalidAttestation);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+
+    // Signature, public key and message must all live inside the Ed25519 instruction itself
+    let this_instruction = u16::MAX as usize;
+    require!(
+        read_u16(4) == this_instruction && read_u16(8) == this_instruction && read_u16(14) == this_instruction,
+        ErrorCode::InvalidAttestation
+    );
+
+    let public_key_offset = read_u16(6);
+    let message_offset = read_u16(10);
+    let message_size = read_u16(12);
+    let signed_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ErrorCode::InvalidAttestation)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidAttestation)?;
+
+    require!(
+        signed_key == oracle.as_ref() && signed_message == message,
+        ErrorCode::InvalidAttestation
+    );
+    Ok(())
+}
+
 impl <'info> CreateDataset <'info> {
     fn update_reputation(&mut self, quality_score: u8) -> Result<()> {
+        let scoring = &self.config.scoring;
         let reputation = &mut self.reputation;
         
         // Update basic stats
@@ -54,10 +172,19 @@ impl <'info> CreateDataset <'info> {
         reputation.total_quality_score = reputation.total_quality_score
             .checked_add(quality_score as u64)
             .ok_or(ErrorCode::NumericalOverflow)?;
-            
+
+        // Uploading counts as activity, which also resets any decay
+        let now = Clock::get()?.unix_timestamp;
+        touch_activity(reputation, now);
+
+        // Tiered upload reward, derived from the dataset's own quality score
+        rescore_upload_points(reputation, &mut self.dataset, scoring)?;
+        calculate_reputation_score(reputation, scoring, now)?;
+
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_dataset (
         &mut self,
         content_hash: [u8; 32],
@@ -68,6 +195,7 @@ impl <'info> CreateDataset <'info> {
         column_count: u64,
         row_count: u64,
         quality_score: u8,
+        nonce: u64,
         // upload_timestamp: i64,
         // last_updated: Option<i64>,
         // download_count: u32,
@@ -75,12 +203,42 @@ impl <'info> CreateDataset <'info> {
         bumps: &CreateDatasetBumps
     ) -> Result<()> {
         require!(content_hash.len() == 32, ErrorCode::HashTooLong);
-        require!(content_hash == content_hash, ErrorCode::DuplicateDataset);
         require!(file_name.len() <= 100, ErrorCode::FileNameTooLong);
         require!(quality_score <= 100, ErrorCode::InvalidQualityScore);
-        require!(file_size <= 104_857_600, ErrorCode::FileTooLarge);
+        require!(file_size <= MAX_FILE_SIZE, ErrorCode::FileTooLarge);
         require!(file_size > 0, ErrorCode::InvalidFileSize);
+        // Judged on the standing decayed to now, before this upload counts as activity
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            file_size <= self.config.scoring.trusted_upload_size
+                || current_tier(&self.reputation, &self.config.scoring, now) >= ContributorTier::Trusted,
+            ErrorCode::InsufficientTier
+        );
         require!(column_count <= 100, ErrorCode::TooManyColumns);
+
+        let message = quality_attestation_message(
+            &self.contributor.key(),
+            &content_hash,
+            quality_score,
+            row_count,
+            column_count,
+            file_size,
+            nonce,
+        );
+        verify_quality_attestation(&self.instructions, &self.quality_oracle.key(), &message)?;
+
+        let dataset_key = self.dataset.key();
+        self.attestation_nonce.set_inner(AttestationNonce {
+            oracle: self.quality_oracle.key(),
+            nonce,
+            dataset: dataset_key,
+            bump: bumps.attestation_nonce,
+        });
+
+        require!(
+            self.hash_index.claim(content_hash, dataset_key, self.contributor.key(), bumps.hash_index),
+            ErrorCode::DuplicateDataset
+        );
         // require!(upload_timestamp <= 104_857_600, ErrorCode::FileTooLarge);
 
 
@@ -94,6 +252,7 @@ impl <'info> CreateDataset <'info> {
         let current_index =self.reputation.dataset_count;
         dataset.dataset_index  = current_index;
 
+        dataset.account_version = Dataset::ACCOUNT_VERSION;
         dataset.id = dataset.key();
         dataset.contributor = self.contributor.key();
         dataset.content_hash = content_hash;
@@ -104,10 +263,16 @@ impl <'info> CreateDataset <'info> {
         dataset.column_count = column_count;
         dataset.row_count = row_count;
         dataset.quality_score = quality_score;
+        dataset.upload_points = 0;
         dataset.upload_timestamp = clock.unix_timestamp;
         dataset.last_updated = None;
+        dataset.version = 1;
         dataset.download_count = 0;
         dataset.is_active = true;
+        dataset.moderated = false;
+        dataset.review_count = 0;
+        dataset.review_score_total = 0;
+        dataset.review_weight_total = 0;
         dataset.bump = bumps.dataset;
 
         registry.total_datasets = registry.total_datasets.checked_add(1).unwrap();
@@ -136,7 +301,7 @@ impl <'info> CreateDataset <'info> {
            contributor: self.contributor.key(),
            action: "upload".to_string(),
            new_dataset_count: self.reputation.dataset_count,
-           new_reputation_score: self.reputation.reputation_score,  // Use existing field
+           new_reputation_score: self.reputation.reputation_score,
        });
         Ok(())
     }
@@ -1,7 +1,9 @@
 #![allow(unexpected_cfgs)]
 use anchor_lang::prelude::*;
 
-use crate::{Registry, Reputation};
+use crate::error::ErrorCode;
+use crate::program::AfricaResearchBase;
+use crate::{ContributorTier, Registry, Reputation};
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -13,11 +15,23 @@ pub struct Initialize<'info> {
 
     #[account(mut)]
     pub contributor: Signer<'info>,
+
+    // Only the deployer may claim the admin seat of the singleton registry
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ ErrorCode::UnauthorizedDeployer
+    )]
+    pub program: Program<'info, AfricaResearchBase>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::UnauthorizedDeployer
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
     #[account(
         init,
         payer = admin,
         space = 8 + Registry::INIT_SPACE,
-        seeds = [b"registry", admin.key().as_ref()],
+        seeds = [b"registry"],
         bump
     )]
     pub registry: Account<'info, Registry>,
@@ -27,9 +41,6 @@ pub struct Initialize<'info> {
 
 #[derive(Accounts)]
 pub struct InitializeReputation<'info> {
-    #[account(mut)]
-    pub admin: Signer<'info>,
-
     #[account(mut)]
     pub user: Signer<'info>,
 
@@ -51,7 +62,9 @@ pub struct InitializeReputation<'info> {
 impl<'info> Initialize<'info> {
     pub fn initialize_registry(&mut self, bumps: &InitializeBumps) -> Result<()> {
         self.registry.set_inner(Registry {
+            account_version: Registry::ACCOUNT_VERSION,
             admin: self.admin.key(),
+            pending_admin: None,
             total_datasets: 0,
             total_downloads: 0,
             bump: bumps.registry,
@@ -137,6 +150,7 @@ impl<'info> Initialize<'info> {
 impl<'info> InitializeReputation<'info> {
     pub fn initialize_reputation(&mut self, bumps: &InitializeReputationBumps) -> Result<()> {
         self.reputation.set_inner(Reputation {
+            account_version: Reputation::ACCOUNT_VERSION,
             contributor: self.contributor.key(),
             total_uploads: 0,
             dataset_count: 0,
@@ -145,10 +159,13 @@ impl<'info> InitializeReputation<'info> {
             total_downloads: 0,
             total_citations: 0,
             reputation_score: 0,
+            earned_points: 0,
+            tier: ContributorTier::Novice,
 
             // Initialize new fields
             total_reviews: 0,
-            last_activity_timestamp: 0,
+            // Decay is measured from here until the first activity
+            last_activity_timestamp: Clock::get()?.unix_timestamp,
             daily_activity_points: 0,
 
             bump: bumps.reputation,
@@ -157,8 +174,13 @@ impl<'info> InitializeReputation<'info> {
             total_upload_points: 0,
             total_review_points: 0,
             total_activity_points: 0,
+            total_download_points: 0,
+            total_citation_points: 0,
+            legacy_upload_points: 0,
 
             claimed_points: 0,
+            redeemable_points: 0,
+            vesting_count: 0,
         });
 
         Ok(())
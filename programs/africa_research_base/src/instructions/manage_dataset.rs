@@ -0,0 +1,165 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::events::{DatasetClosed, DatasetStatusChanged};
+use crate::instructions::roles::holds_role;
+use crate::instructions::update_reputation::{calculate_reputation_score, uncredit_dataset};
+use crate::{
+    Authority, ContentHashIndex, Dataset, DatasetVersion, PauseCategory, ProgramConfig, Registry,
+    Reputation, Role,
+};
+
+#[derive(Accounts)]
+pub struct SetDatasetStatus<'info> {
+    // Either the dataset's contributor or a Moderator
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [b"authority", registry.key().as_ref(), signer.key().as_ref()],
+        bump = signer_authority.bump
+    )]
+    pub signer_authority: Option<Account<'info, Authority>>,
+
+    #[account(
+        mut,
+        seeds = [b"dataset", dataset.contributor.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDataset<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(PauseCategory::Datasets) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        close = contributor,
+        seeds = [b"dataset", contributor.key().as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        has_one = contributor @ ErrorCode::UnauthorizedUpdate
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    // Releases the current hash so the same file can be registered again
+    #[account(
+        mut,
+        close = contributor,
+        seeds = [b"hash", dataset.content_hash.as_ref()],
+        bump = hash_index.bump,
+        constraint = hash_index.dataset == dataset.key()
+    )]
+    pub hash_index: Account<'info, ContentHashIndex>,
+
+    #[account(
+        mut,
+        seeds = [b"reputation", contributor.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+}
+
+impl<'info> SetDatasetStatus<'info> {
+    pub fn set_dataset_status(&mut self, is_active: bool) -> Result<()> {
+        let is_contributor = self.signer.key() == self.dataset.contributor;
+        let is_moderator = holds_role(&self.signer_authority, Role::Moderator);
+        require!(is_contributor || is_moderator, ErrorCode::UnauthorizedUpdate);
+
+        let dataset = &mut self.dataset;
+        if is_contributor {
+            // A contributor cannot undo a moderator's takedown
+            require!(!(is_active && dataset.moderated), ErrorCode::UnauthorizedUpdate);
+        } else {
+            dataset.moderated = !is_active;
+        }
+        dataset.is_active = is_active;
+
+        emit!(DatasetStatusChanged {
+            id: dataset.key(),
+            is_active,
+            updated_by: self.signer.key(),
+        });
+        Ok(())
+    }
+}
+
+impl<'info> CloseDataset<'info> {
+    // `superseded` holds a (DatasetVersion, ContentHashIndex) pair for every version before the
+    // current one, in order, so no archived record or hash index outlives the dataset
+    pub fn close_dataset(&mut self, superseded: &'info [AccountInfo<'info>]) -> Result<()> {
+        self.close_superseded_versions(superseded)?;
+
+        let registry = &mut self.registry;
+        registry.total_datasets = registry
+            .total_datasets
+            .checked_sub(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        let reputation = &mut self.reputation;
+        uncredit_dataset(reputation, &self.dataset)?;
+        calculate_reputation_score(reputation, &self.config.scoring, Clock::get()?.unix_timestamp)?;
+
+        emit!(DatasetClosed {
+            id: self.dataset.key(),
+            contributor: self.contributor.key(),
+        });
+        Ok(())
+    }
+
+    fn close_superseded_versions(&self, superseded: &'info [AccountInfo<'info>]) -> Result<()> {
+        let dataset_key = self.dataset.key();
+        let archived = self.dataset.version.saturating_sub(1) as usize;
+        require!(superseded.len() == archived * 2, ErrorCode::IncompleteVersionHistory);
+
+        let contributor = self.contributor.to_account_info();
+        for (index, pair) in superseded.chunks_exact(2).enumerate() {
+            let dataset_version = Account::<DatasetVersion>::try_from(&pair[0])?;
+            require!(
+                dataset_version.dataset == dataset_key && dataset_version.version as usize == index + 1,
+                ErrorCode::IncompleteVersionHistory
+            );
+            let content_hash = dataset_version.content_hash;
+            dataset_version.close(contributor.clone())?;
+
+            // A hash can recur across versions, and the current one is closed through `hash_index`
+            if content_hash == self.dataset.content_hash {
+                continue;
+            }
+            if pair[1].data_is_empty() {
+                let (index_address, _) =
+                    Pubkey::find_program_address(&[b"hash", content_hash.as_ref()], &crate::ID);
+                require_keys_eq!(pair[1].key(), index_address, ErrorCode::IncompleteVersionHistory);
+                continue;
+            }
+            let hash_index = Account::<ContentHashIndex>::try_from(&pair[1])?;
+            require!(
+                hash_index.content_hash == content_hash && hash_index.dataset == dataset_key,
+                ErrorCode::IncompleteVersionHistory
+            );
+            hash_index.close(contributor.clone())?;
+        }
+        Ok(())
+    }
+}
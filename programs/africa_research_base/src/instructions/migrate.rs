@@ -0,0 +1,565 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+use crate::error::ErrorCode;
+use crate::events::{AccountMigrated, ReputationMigrated};
+use crate::instructions::update_reputation::calculate_reputation_score;
+use crate::{
+    ContributorTier, Dataset, EarlyReputation, LegacyDataset, LegacyReputation, ProgramConfig,
+    Reputation, ScoringParams,
+};
+
+// Migrations rewrite an account in place at the current `INIT_SPACE`. The layouts they
+// read predate the `account_version` header and are told apart by their size, which is
+// fixed for every layout because accounts are always allocated at their maximum space.
+// Legacy registries live at a different address and go through `migrate_registry`.
+
+#[derive(Accounts)]
+pub struct MigrateReputation<'info> {
+    // Anyone may migrate an account; the payer only covers the extra rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Only used to derive the reputation address
+    pub contributor: UncheckedAccount<'info>,
+
+    /// CHECK: Pre-migration reputation; discriminator and size are verified in the handler
+    #[account(
+        mut,
+        seeds = [b"reputation", contributor.key().as_ref()],
+        bump,
+        owner = crate::ID @ ErrorCode::UnsupportedAccountLayout
+    )]
+    pub reputation: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Only this program can write an account carrying the dataset discriminator
+#[derive(Accounts)]
+pub struct MigrateDataset<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Pre-migration dataset; discriminator and size are verified in the handler
+    #[account(
+        mut,
+        owner = crate::ID @ ErrorCode::UnsupportedAccountLayout
+    )]
+    pub dataset: UncheckedAccount<'info>,
+
+    // The dataset's contributor, already migrated; checked against the dataset in the handler
+    #[account(
+        mut,
+        seeds = [b"reputation", reputation.contributor.as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Rejects accounts of another type and accounts already on the current layout
+fn check_layout(data: &[u8], discriminator: &[u8], space: usize, version: u8) -> Result<()> {
+    require!(
+        data.len() > 8 && data.starts_with(discriminator),
+        ErrorCode::UnsupportedAccountLayout
+    );
+    require!(
+        !(data.len() == space && data[8] == version),
+        ErrorCode::AccountAlreadyMigrated
+    );
+    Ok(())
+}
+
+fn deserialize_legacy<T: AnchorDeserialize>(data: &[u8]) -> Result<T> {
+    T::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::UnsupportedAccountLayout))
+}
+
+pub(crate) fn migrate_dataset_data(
+    data: &[u8],
+    scoring: &ScoringParams,
+    reputation: &mut Reputation,
+) -> Result<Dataset> {
+    let space = 8 + Dataset::INIT_SPACE;
+    check_layout(data, Dataset::DISCRIMINATOR, space, Dataset::ACCOUNT_VERSION)?;
+    require!(
+        data.len() == 8 + LegacyDataset::INIT_SPACE,
+        ErrorCode::UnsupportedAccountLayout
+    );
+
+    let legacy: LegacyDataset = deserialize_legacy(data)?;
+    require!(
+        legacy.contributor == reputation.contributor,
+        ErrorCode::InvalidReputationUpdate
+    );
+
+    // Only upload points the reputation carried over can be recorded on the dataset. The
+    // baseline did not always credit uploads, so whatever is left unbacked is recorded as 0
+    // and credited by the next rescore rather than taken back out on close.
+    let upload_points = scoring
+        .upload_reward(legacy.quality_score)
+        .min(reputation.legacy_upload_points.min(u32::MAX as u64) as u32);
+    reputation.legacy_upload_points -= upload_points as u64;
+
+    Ok(Dataset {
+        account_version: Dataset::ACCOUNT_VERSION,
+        id: legacy.id,
+        contributor: legacy.contributor,
+        content_hash: legacy.content_hash,
+        ai_metadata: legacy.ai_metadata,
+        file_name: legacy.file_name,
+        dataset_index: legacy.dataset_index,
+        file_size: legacy.file_size,
+        data_uri: legacy.data_uri,
+        column_count: legacy.column_count,
+        row_count: legacy.row_count,
+        quality_score: legacy.quality_score,
+        upload_points,
+        upload_timestamp: legacy.upload_timestamp,
+        last_updated: legacy.last_updated,
+        version: 1,
+        download_count: legacy.download_count,
+        is_active: legacy.is_active,
+        moderated: false,
+        review_count: 0,
+        review_score_total: 0,
+        review_weight_total: 0,
+        bump: legacy.bump,
+    })
+}
+
+// Older layouts have their score rebuilt from the accumulators, since a stored u32 may have
+// wrapped, and their redeemable balance opens at whatever had not been claimed yet
+pub(crate) fn migrate_reputation_data(
+    data: &[u8],
+    scoring: &ScoringParams,
+    now: i64,
+) -> Result<Reputation> {
+    let space = 8 + Reputation::INIT_SPACE;
+    check_layout(data, Reputation::DISCRIMINATOR, space, Reputation::ACCOUNT_VERSION)?;
+
+    // Older layouts only kept counts, so the downloads and citations they hold are priced
+    // at the weights in force at migration
+    let download_points = |downloads: u64| downloads.saturating_mul(scoring.download_weight as u64);
+    let citation_points = |citations: u32| citations as u64 * scoring.citation_weight as u64;
+
+    let mut reputation = if data.len() == 8 + LegacyReputation::INIT_SPACE {
+        let legacy: LegacyReputation = deserialize_legacy(data)?;
+        Reputation {
+            account_version: Reputation::ACCOUNT_VERSION,
+            contributor: legacy.contributor,
+            total_uploads: legacy.total_uploads,
+            dataset_count: legacy.dataset_count,
+            download_time: legacy.download_time,
+            total_quality_score: legacy.total_quality_score,
+            total_downloads: legacy.total_downloads,
+            total_citations: legacy.total_citations,
+            reputation_score: 0,
+            earned_points: 0,
+            tier: ContributorTier::Novice,
+            total_reviews: legacy.total_reviews,
+            last_activity_timestamp: legacy.last_activity_timestamp,
+            daily_activity_points: legacy.daily_activity_points,
+            total_upload_points: legacy.total_upload_points,
+            total_review_points: legacy.total_review_points,
+            total_activity_points: legacy.total_activity_points,
+            total_download_points: download_points(legacy.total_downloads),
+            total_citation_points: citation_points(legacy.total_citations),
+            legacy_upload_points: legacy.total_upload_points,
+            claimed_points: legacy.claimed_points,
+            redeemable_points: 0,
+            vesting_count: 0,
+            bump: legacy.bump,
+        }
+    } else if data.len() == 8 + EarlyReputation::INIT_SPACE {
+        let early: EarlyReputation = deserialize_legacy(data)?;
+        // No accumulators yet: whatever the stored score holds beyond downloads and
+        // citations was earned through uploads
+        let total_download_points = download_points(early.total_downloads);
+        let total_citation_points = citation_points(early.total_citations);
+        let weighted = total_download_points.saturating_add(total_citation_points);
+        let upload_points = (early.reputation_score as u64).saturating_sub(weighted);
+        Reputation {
+            account_version: Reputation::ACCOUNT_VERSION,
+            contributor: early.contributor,
+            total_uploads: early.total_uploads,
+            // Every upload took the next seed index
+            dataset_count: early.total_uploads,
+            download_time: early.download_time,
+            total_quality_score: early.total_quality_score,
+            total_downloads: early.total_downloads,
+            total_citations: early.total_citations,
+            reputation_score: 0,
+            earned_points: 0,
+            tier: ContributorTier::Novice,
+            total_reviews: 0,
+            last_activity_timestamp: 0,
+            daily_activity_points: 0,
+            total_upload_points: upload_points,
+            total_review_points: 0,
+            total_activity_points: 0,
+            total_download_points,
+            total_citation_points,
+            legacy_upload_points: upload_points,
+            claimed_points: 0,
+            redeemable_points: 0,
+            vesting_count: 0,
+            bump: early.bump,
+        }
+    } else {
+        return err!(ErrorCode::UnsupportedAccountLayout);
+    };
+
+    calculate_reputation_score(&mut reputation, scoring, now)?;
+    Ok(reputation)
+}
+
+// Tops up rent from `payer`, reallocs `account` to `space` and writes `value` over it
+fn write_migrated<'info, T: AccountSerialize>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+    value: &T,
+    account_version: u8,
+) -> Result<()> {
+    let previous_size = account.data_len() as u32;
+    let shortfall = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if shortfall > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: payer.to_account_info(),
+            to: account.clone(),
+        };
+        let cpi_ctx = CpiContext::new(system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, shortfall)?;
+    }
+    account.resize(space)?;
+    value.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+    emit!(AccountMigrated {
+        account: account.key(),
+        previous_size,
+        account_version,
+    });
+    Ok(())
+}
+
+impl<'info> MigrateReputation<'info> {
+    pub fn migrate_reputation(&mut self) -> Result<()> {
+        let reputation = migrate_reputation_data(
+            &self.reputation.try_borrow_data()?,
+            &self.config.scoring,
+            Clock::get()?.unix_timestamp,
+        )?;
+        write_migrated(
+            &self.reputation.to_account_info(),
+            &self.payer,
+            &self.system_program,
+            8 + Reputation::INIT_SPACE,
+            &reputation,
+            Reputation::ACCOUNT_VERSION,
+        )?;
+
+        emit!(ReputationMigrated {
+            contributor: reputation.contributor,
+            reputation_score: reputation.reputation_score,
+            redeemable_points: reputation.redeemable_points,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> MigrateDataset<'info> {
+    pub fn migrate_dataset(&mut self) -> Result<()> {
+        let dataset = migrate_dataset_data(
+            &self.dataset.try_borrow_data()?,
+            &self.config.scoring,
+            &mut self.reputation,
+        )?;
+        write_migrated(
+            &self.dataset.to_account_info(),
+            &self.payer,
+            &self.system_program,
+            8 + Dataset::INIT_SPACE,
+            &dataset,
+            Dataset::ACCOUNT_VERSION,
+        )
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::update_reputation::uncredit_dataset;
+
+    // Raw account data as written by an older layout: discriminator, fields, zero padding
+    fn legacy_bytes<T: AnchorSerialize>(discriminator: &[u8], value: &T, space: usize) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        value.serialize(&mut data).unwrap();
+        data.resize(space, 0);
+        data
+    }
+
+    fn legacy_reputation() -> LegacyReputation {
+        LegacyReputation {
+            contributor: Pubkey::new_unique(),
+            total_uploads: 3,
+            dataset_count: 3,
+            download_time: 1_700_000_000,
+            total_quality_score: 240,
+            total_downloads: 2,
+            total_citations: 1,
+            // Wrapped: the accumulators below add up to far more
+            reputation_score: 7,
+            total_reviews: 1,
+            last_activity_timestamp: 1_700_000_100,
+            daily_activity_points: 20,
+            total_upload_points: u32::MAX as u64,
+            total_review_points: 50,
+            total_activity_points: 20,
+            claimed_points: 100,
+            bump: 254,
+        }
+    }
+
+    #[test]
+    fn migrates_u32_reputation_and_rebuilds_the_score() {
+        let legacy = legacy_reputation();
+        let data = legacy_bytes(
+            Reputation::DISCRIMINATOR,
+            &legacy,
+            8 + LegacyReputation::INIT_SPACE,
+        );
+        let scoring = ScoringParams::default();
+
+        let reputation = migrate_reputation_data(&data, &scoring, 1_700_000_200).unwrap();
+        let expected_score = u32::MAX as u64
+            + 50
+            + 20
+            + 2 * scoring.download_weight as u64
+            + scoring.citation_weight as u64;
+        assert_eq!(reputation.account_version, Reputation::ACCOUNT_VERSION);
+        assert_eq!(reputation.contributor, legacy.contributor);
+        assert_eq!(reputation.dataset_count, 3);
+        assert_eq!(reputation.total_reviews, 1);
+        assert_eq!(reputation.reputation_score, expected_score);
+        assert_eq!(reputation.earned_points, expected_score);
+        assert_eq!(reputation.claimed_points, 100);
+        assert_eq!(reputation.redeemable_points, expected_score - 100);
+        assert_eq!(reputation.bump, 254);
+    }
+
+    #[test]
+    fn migrates_early_reputation_with_defaults() {
+        let early = EarlyReputation {
+            contributor: Pubkey::new_unique(),
+            total_uploads: 4,
+            download_time: 1_690_000_000,
+            total_quality_score: 300,
+            total_downloads: 5,
+            total_citations: 2,
+            reputation_score: 1_000,
+            bump: 251,
+        };
+        let data = legacy_bytes(Reputation::DISCRIMINATOR, &early, 8 + EarlyReputation::INIT_SPACE);
+        let scoring = ScoringParams::default();
+
+        let reputation = migrate_reputation_data(&data, &scoring, 1_700_000_200).unwrap();
+        let weighted = 5 * scoring.download_weight as u64 + 2 * scoring.citation_weight as u64;
+        assert_eq!(reputation.account_version, Reputation::ACCOUNT_VERSION);
+        assert_eq!(reputation.dataset_count, 4);
+        assert_eq!(reputation.total_upload_points, 1_000 - weighted);
+        assert_eq!(reputation.reputation_score, 1_000);
+        assert_eq!(reputation.redeemable_points, 1_000);
+        assert_eq!(reputation.total_reviews, 0);
+        assert_eq!(reputation.claimed_points, 0);
+        assert_eq!(reputation.bump, 251);
+    }
+
+    #[test]
+    fn decay_does_not_shrink_the_migrated_balance() {
+        let legacy = legacy_reputation();
+        let data = legacy_bytes(
+            Reputation::DISCRIMINATOR,
+            &legacy,
+            8 + LegacyReputation::INIT_SPACE,
+        );
+        let scoring = ScoringParams {
+            decay_enabled: true,
+            decay_period: 86_400,
+            decay_rate_bps: 5_000,
+            ..ScoringParams::default()
+        };
+
+        // Idle for two full periods since the last recorded activity
+        let now = legacy.last_activity_timestamp + 2 * 86_400;
+        let reputation = migrate_reputation_data(&data, &scoring, now).unwrap();
+        assert_eq!(reputation.reputation_score, reputation.earned_points / 4);
+        assert_eq!(reputation.redeemable_points, reputation.earned_points - 100);
+    }
+
+    fn legacy_dataset(contributor: Pubkey) -> LegacyDataset {
+        LegacyDataset {
+            id: Pubkey::new_unique(),
+            contributor,
+            content_hash: [7; 32],
+            ai_metadata: b"{\"topic\":\"rainfall\"}".to_vec(),
+            file_name: b"rainfall.csv".to_vec(),
+            dataset_index: 2,
+            file_size: 4_096,
+            data_uri: [9; 256],
+            column_count: 8,
+            row_count: 1_000,
+            quality_score: 85,
+            upload_timestamp: 1_700_000_000,
+            last_updated: Some(1_700_000_500),
+            download_count: 6,
+            is_active: true,
+            bump: 250,
+        }
+    }
+
+    // A baseline reputation migrated to the current layout
+    fn migrated_reputation(total_upload_points: u64) -> Reputation {
+        let legacy = LegacyReputation {
+            total_upload_points,
+            ..legacy_reputation()
+        };
+        let data = legacy_bytes(
+            Reputation::DISCRIMINATOR,
+            &legacy,
+            8 + LegacyReputation::INIT_SPACE,
+        );
+        migrate_reputation_data(&data, &ScoringParams::default(), 1_700_000_200).unwrap()
+    }
+
+    #[test]
+    fn migrates_legacy_dataset_with_defaults() {
+        let scoring = ScoringParams::default();
+        let mut reputation = migrated_reputation(1_000);
+        let legacy = legacy_dataset(reputation.contributor);
+        let data = legacy_bytes(Dataset::DISCRIMINATOR, &legacy, 8 + LegacyDataset::INIT_SPACE);
+
+        let dataset = migrate_dataset_data(&data, &scoring, &mut reputation).unwrap();
+        assert_eq!(dataset.account_version, Dataset::ACCOUNT_VERSION);
+        assert_eq!(dataset.id, legacy.id);
+        assert_eq!(dataset.ai_metadata, legacy.ai_metadata);
+        assert_eq!(dataset.file_name, legacy.file_name);
+        assert_eq!(dataset.data_uri, legacy.data_uri);
+        assert_eq!(dataset.quality_score, 85);
+        assert_eq!(dataset.upload_points, scoring.upload_reward(85));
+        assert_eq!(dataset.last_updated, Some(1_700_000_500));
+        assert_eq!(dataset.download_count, 6);
+        assert_eq!(dataset.version, 1);
+        assert!(!dataset.moderated);
+        assert_eq!(dataset.review_count, 0);
+        assert_eq!(dataset.bump, 250);
+        assert_eq!(
+            reputation.legacy_upload_points,
+            1_000 - scoring.upload_reward(85) as u64
+        );
+    }
+
+    #[test]
+    fn unbacked_legacy_dataset_can_still_be_closed() {
+        let scoring = ScoringParams::default();
+        // Baseline uploads were never credited, so the reputation holds no upload points
+        let mut reputation = migrated_reputation(0);
+        let data = legacy_bytes(
+            Dataset::DISCRIMINATOR,
+            &legacy_dataset(reputation.contributor),
+            8 + LegacyDataset::INIT_SPACE,
+        );
+
+        let dataset = migrate_dataset_data(&data, &scoring, &mut reputation).unwrap();
+        assert_eq!(dataset.upload_points, 0);
+
+        uncredit_dataset(&mut reputation, &dataset).unwrap();
+        assert_eq!(reputation.total_upload_points, 0);
+        assert_eq!(reputation.total_uploads, 2);
+    }
+
+    #[test]
+    fn partly_backed_legacy_datasets_share_the_carried_points() {
+        let scoring = ScoringParams::default();
+        let reward = scoring.upload_reward(85);
+        let mut reputation = migrated_reputation(reward as u64 + 1);
+        let data = legacy_bytes(
+            Dataset::DISCRIMINATOR,
+            &legacy_dataset(reputation.contributor),
+            8 + LegacyDataset::INIT_SPACE,
+        );
+
+        let first = migrate_dataset_data(&data, &scoring, &mut reputation).unwrap();
+        let second = migrate_dataset_data(&data, &scoring, &mut reputation).unwrap();
+        assert_eq!(first.upload_points, reward);
+        assert_eq!(second.upload_points, 1);
+        assert_eq!(reputation.legacy_upload_points, 0);
+    }
+
+    #[test]
+    fn rejects_a_dataset_of_another_contributor() {
+        let mut reputation = migrated_reputation(1_000);
+        let data = legacy_bytes(
+            Dataset::DISCRIMINATOR,
+            &legacy_dataset(Pubkey::new_unique()),
+            8 + LegacyDataset::INIT_SPACE,
+        );
+
+        let result = migrate_dataset_data(&data, &ScoringParams::default(), &mut reputation);
+        assert_eq!(result.err().unwrap(), ErrorCode::InvalidReputationUpdate.into());
+    }
+
+    #[test]
+    fn rejects_current_accounts() {
+        let legacy = legacy_reputation();
+        let data = legacy_bytes(
+            Reputation::DISCRIMINATOR,
+            &legacy,
+            8 + LegacyReputation::INIT_SPACE,
+        );
+        let scoring = ScoringParams::default();
+        let reputation = migrate_reputation_data(&data, &scoring, 1_700_000_200).unwrap();
+
+        let mut data = Vec::new();
+        reputation.try_serialize(&mut data).unwrap();
+        data.resize(8 + Reputation::INIT_SPACE, 0);
+        let result = migrate_reputation_data(&data, &scoring, 1_700_000_200);
+        assert_eq!(result.err().unwrap(), ErrorCode::AccountAlreadyMigrated.into());
+    }
+
+    #[test]
+    fn rejects_other_account_types_and_unknown_sizes() {
+        let mut data = legacy_bytes(
+            Reputation::DISCRIMINATOR,
+            &legacy_reputation(),
+            8 + LegacyReputation::INIT_SPACE,
+        );
+        let mut reputation = migrated_reputation(0);
+        let result = migrate_dataset_data(&data, &ScoringParams::default(), &mut reputation);
+        assert_eq!(result.err().unwrap(), ErrorCode::UnsupportedAccountLayout.into());
+
+        data.push(0);
+        data.push(0);
+        let result = migrate_reputation_data(&data, &ScoringParams::default(), 0);
+        assert_eq!(result.err().unwrap(), ErrorCode::UnsupportedAccountLayout.into());
+    }
+}
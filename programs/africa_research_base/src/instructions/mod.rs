@@ -4,11 +4,53 @@ pub use initialize::*;
 pub mod create_dataset;
 pub use create_dataset::*;
 
+pub mod update_dataset;
+pub use update_dataset::*;
+
+pub mod manage_dataset;
+pub use manage_dataset::*;
+
 pub mod update_reputation;
 pub use update_reputation::*;
 
+pub mod record_download;
+pub use record_download::*;
+
+pub mod submit_review;
+pub use submit_review::*;
+
+pub mod recalculate_quality;
+pub use recalculate_quality::*;
+
+pub mod refresh_reputation;
+pub use refresh_reputation::*;
+
+pub mod review_stake;
+pub use review_stake::*;
+
+pub mod reward_vault;
+pub use reward_vault::*;
+
 pub mod redeem;
 pub use redeem::*;
 
-// pub mod record_citation;
-// pub use record_citation::*;
+pub mod vesting;
+pub use vesting::*;
+
+pub mod roles;
+pub use roles::*;
+
+pub mod admin;
+pub use admin::*;
+
+pub mod migrate;
+pub use migrate::*;
+
+pub mod update_config;
+pub use update_config::*;
+
+pub mod pause;
+pub use pause::*;
+
+pub mod record_citation;
+pub use record_citation::*;
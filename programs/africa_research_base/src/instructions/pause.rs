@@ -0,0 +1,51 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::events::PauseStateChanged;
+use crate::instructions::roles::require_super_admin;
+use crate::{Authority, PauseCategory, ProgramConfig, Registry};
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [b"authority", registry.key().as_ref(), admin.key().as_ref()],
+        bump = admin_authority.bump
+    )]
+    pub admin_authority: Option<Account<'info, Authority>>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+impl<'info> SetPause<'info> {
+    // `None` toggles the global switch, `Some(category)` a single instruction category
+    pub fn set_pause(&mut self, category: Option<PauseCategory>, paused: bool) -> Result<()> {
+        require_super_admin(&self.registry, &self.admin.key(), &self.admin_authority)?;
+
+        let config = &mut self.config;
+        match category {
+            None => config.paused = paused,
+            Some(category) if paused => config.paused_categories |= category.mask(),
+            Some(category) => config.paused_categories &= !category.mask(),
+        }
+
+        emit!(PauseStateChanged {
+            updated_by: self.admin.key(),
+            paused: config.paused,
+            paused_categories: config.paused_categories,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::events::{QualityScoreRecalculated, ReputationUpdated};
+use crate::instructions::update_reputation::{calculate_reputation_score, rescore_upload_points};
+use crate::{Dataset, PauseCategory, ProgramConfig, Reputation};
+
+// Permissionless: the result depends only on review totals already recorded on the dataset
+#[derive(Accounts)]
+pub struct RecalculateQualityScore<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(PauseCategory::Reputation) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"dataset", dataset.contributor.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = dataset.is_active @ ErrorCode::DatasetInactive
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        mut,
+        seeds = [b"reputation", dataset.contributor.as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+}
+
+impl<'info> RecalculateQualityScore<'info> {
+    pub fn recalculate_quality_score(&mut self) -> Result<()> {
+        let dataset = &mut self.dataset;
+        require!(dataset.review_weight_total > 0, ErrorCode::NoReviews);
+
+        // Rubric scores are at most 100, so the weighted mean always fits in a u8
+        let previous_score = dataset.quality_score;
+        let new_score = (dataset.review_score_total / dataset.review_weight_total) as u8;
+        dataset.quality_score = new_score;
+
+        let scoring = &self.config.scoring;
+        let reputation = &mut self.reputation;
+        reputation.total_quality_score = reputation
+            .total_quality_score
+            .checked_sub(previous_score as u64)
+            .ok_or(ErrorCode::NumericalOverflow)?
+            .checked_add(new_score as u64)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        // Moves the contributor's upload points if the dataset changed tier
+        rescore_upload_points(reputation, dataset, scoring)?;
+        calculate_reputation_score(reputation, scoring, Clock::get()?.unix_timestamp)?;
+
+        emit!(QualityScoreRecalculated {
+            dataset: dataset.key(),
+            previous_score,
+            new_score,
+            review_count: dataset.review_count,
+            upload_points: dataset.upload_points,
+        });
+
+        emit!(ReputationUpdated {
+            contributor: dataset.contributor,
+            action: "quality_recalculation".to_string(),
+            new_dataset_count: reputation.dataset_count,
+            new_reputation_score: reputation.reputation_score,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::constants::SECONDS_PER_YEAR;
+use crate::error::ErrorCode;
+use crate::events::{CitationRecorded, ReputationUpdated};
+use crate::instructions::update_reputation::{calculate_reputation_score, credit_citation};
+use crate::{Citation, Dataset, PauseCategory, ProgramConfig, PublicationReference, Reputation};
+
+#[derive(Accounts)]
+#[instruction(publication_hash: [u8; 32])]
+pub struct RecordCitation<'info> {
+    #[account(mut)]
+    pub citer: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(PauseCategory::Reputation) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [b"dataset", dataset.contributor.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = dataset.is_active @ ErrorCode::DatasetInactive,
+        constraint = dataset.contributor != citer.key() @ ErrorCode::SelfCitation
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    // One record per (dataset, citer, publication), so the same paper only counts once
+    #[account(
+        init,
+        payer = citer,
+        space = 8 + Citation::INIT_SPACE,
+        seeds = [b"citation", dataset.key().as_ref(), citer.key().as_ref(), publication_hash.as_ref()],
+        bump
+    )]
+    pub citation: Account<'info, Citation>,
+
+    #[account(
+        mut,
+        seeds = [b"reputation", dataset.contributor.as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RecordCitation<'info> {
+    pub fn record_citation(
+        &mut self,
+        publication_hash: [u8; 32],
+        reference: PublicationReference,
+        bumps: &RecordCitationBumps,
+    ) -> Result<()> {
+        let citing_time = Clock::get()?.unix_timestamp;
+        let current_year = (1970 + citing_time / SECONDS_PER_YEAR) as u16;
+
+        require!(reference.is_valid(current_year), ErrorCode::InvalidPublication);
+        // The PDA is keyed by the hash, so it must be the dedup key of the stored reference
+        require!(
+            reference.publication_hash() == publication_hash,
+            ErrorCode::InvalidPublication
+        );
+
+        let dataset_key = self.dataset.key();
+        let contributor = self.dataset.contributor;
+
+        self.citation.set_inner(Citation {
+            account_version: Citation::ACCOUNT_VERSION,
+            dataset_id: dataset_key,
+            citer: self.citer.key(),
+            contributor,
+            publication_hash,
+            reference,
+            citing_time,
+            bump: bumps.citation,
+        });
+
+        let scoring = &self.config.scoring;
+        let reputation = &mut self.reputation;
+        credit_citation(reputation, scoring)?;
+        calculate_reputation_score(reputation, scoring, citing_time)?;
+
+        emit!(CitationRecorded {
+            dataset_id: dataset_key,
+            citer: self.citer.key(),
+            contributor,
+            publication_hash,
+            citing_time,
+        });
+
+        emit!(ReputationUpdated {
+            contributor,
+            action: "citation".to_string(),
+            new_dataset_count: reputation.dataset_count,
+            new_reputation_score: reputation.reputation_score,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::events::{DownloadRecorded, ReputationUpdated};
+use crate::instructions::update_reputation::{calculate_reputation_score, credit_download};
+use crate::{Attribution, Dataset, PauseCategory, ProgramConfig, Registry, Reputation};
+
+#[derive(Accounts)]
+pub struct RecordDownload<'info> {
+    #[account(mut)]
+    pub downloader: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(PauseCategory::Reputation) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"dataset", dataset.contributor.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = dataset.is_active @ ErrorCode::DatasetInactive,
+        constraint = dataset.contributor != downloader.key() @ ErrorCode::SelfDownload
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    // One record per (dataset, downloader), so a wallet can only credit a dataset once
+    #[account(
+        init,
+        payer = downloader,
+        space = 8 + Attribution::INIT_SPACE,
+        seeds = [b"attribution", dataset.key().as_ref(), downloader.key().as_ref()],
+        bump
+    )]
+    pub attribution: Account<'info, Attribution>,
+
+    #[account(
+        mut,
+        seeds = [b"reputation", dataset.contributor.as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RecordDownload<'info> {
+    pub fn record_download(&mut self, bumps: &RecordDownloadBumps) -> Result<()> {
+        let download_time = Clock::get()?.unix_timestamp;
+        let dataset_key = self.dataset.key();
+        let contributor = self.dataset.contributor;
+
+        self.attribution.set_inner(Attribution {
+            account_version: Attribution::ACCOUNT_VERSION,
+            dataset_id: dataset_key,
+            downloader: self.downloader.key(),
+            contributor,
+            download_time,
+            bump: bumps.attribution,
+        });
+
+        self.dataset.download_count = self
+            .dataset
+            .download_count
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        self.registry.total_downloads = self
+            .registry
+            .total_downloads
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        let scoring = &self.config.scoring;
+        let reputation = &mut self.reputation;
+        credit_download(reputation, scoring, download_time)?;
+        calculate_reputation_score(reputation, scoring, download_time)?;
+
+        emit!(DownloadRecorded {
+            dataset_id: dataset_key,
+            downloader: self.downloader.key(),
+            contributor,
+            download_time,
+        });
+
+        emit!(ReputationUpdated {
+            contributor,
+            action: "download".to_string(),
+            new_dataset_count: reputation.dataset_count,
+            new_reputation_score: reputation.reputation_score,
+        });
+
+        Ok(())
+    }
+}
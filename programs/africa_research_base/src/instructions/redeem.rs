@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
 
-use crate::constants::POINTS_TO_ARB_RATE;
 use crate::error::ErrorCode;
-use crate::state::Reputation;
+use crate::events::PointsRedeemed;
+use crate::state::{PauseCategory, ProgramConfig, Reputation, ScoringParams};
 
 #[derive(Accounts)]
 pub struct RedeemPoints<'info> {
@@ -18,10 +19,31 @@ pub struct RedeemPoints<'info> {
     )]
     pub reputation: Account<'info, Reputation>,
 
-    #[account(mut)]
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(PauseCategory::Redemption) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        address = config.arb_mint @ ErrorCode::InvalidMint
+    )]
+    pub arb_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = config.reward_vault @ ErrorCode::InvalidRewardVault
+    )]
     pub reward_vault: Account<'info, TokenAccount>,
 
-    #[account(mut)]
+    // Always the user's own ATA for the ARB mint, created on first redemption
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = arb_mint,
+        associated_token::authority = user
+    )]
     pub user_token_account: Account<'info, TokenAccount>,
 
     /// CHECK: The authority allowed to transfer from the vault
@@ -32,49 +54,82 @@ pub struct RedeemPoints<'info> {
     pub vault_authority: AccountInfo<'info>,
 
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
-impl<'info> RedeemPoints<'info> {
-    pub fn redeem_points(ctx: Context<RedeemPoints>) -> Result<()> {
-        let reputation = &mut ctx.accounts.reputation;
-
-        // Calculate available points
-        let total_score = reputation.reputation_score as u64;
-        let claimed = reputation.claimed_points;
-
-        if total_score <= claimed {
-            return err!(ErrorCode::NumericalOverflow); // Use better error in prod
-        }
-
-        let available_points = total_score - claimed;
-        let amount_to_transfer = available_points
-            .checked_mul(POINTS_TO_ARB_RATE)
-            .ok_or(ErrorCode::NumericalOverflow)?;
-
-        if amount_to_transfer == 0 {
-            return Ok(());
-        }
+// Checks a claim of `points` against the claim bounds and the vault balance, moves the
+// points from the redeemable balance to claimed and returns the ARB owed plus the points still redeemable
+pub(crate) fn debit_points(
+    reputation: &mut Reputation,
+    scoring: &ScoringParams,
+    points: u64,
+    decimals: u8,
+    vault_balance: u64,
+) -> Result<(u64, u64)> {
+    let available_points = reputation.redeemable_points;
+
+    require!(available_points > 0, ErrorCode::NothingToRedeem);
+    // A balance left below the minimum can still be claimed in full
+    require!(
+        points >= scoring.min_redeem_points || (points > 0 && points == available_points),
+        ErrorCode::RedeemBelowMinimum
+    );
+    require!(points <= scoring.max_redeem_points, ErrorCode::RedeemAboveMaximum);
+    require!(points <= available_points, ErrorCode::RedeemExceedsBalance);
+
+    let token_amount = scoring
+        .points_to_tokens(points, decimals)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+    // A rate that rounds the claim down to nothing would burn the points for no ARB
+    require!(token_amount > 0, ErrorCode::RedeemBelowMinimum);
+    require!(vault_balance >= token_amount, ErrorCode::VaultInsufficientFunds);
+
+    reputation.redeemable_points = available_points - points;
+    reputation.claimed_points = reputation
+        .claimed_points
+        .checked_add(points)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+
+    Ok((token_amount, available_points - points))
+}
 
-        // Update claimed points
-        reputation.claimed_points = reputation
-            .claimed_points
-            .checked_add(available_points)
-            .ok_or(ErrorCode::NumericalOverflow)?;
+impl<'info> RedeemPoints<'info> {
+    // Redeems `amount` points; the rest stay redeemable later
+    pub fn redeem_points(ctx: Context<RedeemPoints>, amount: u64) -> Result<()> {
+        let scoring = &ctx.accounts.config.scoring;
+        require!(!scoring.vesting_enabled, ErrorCode::VestingRequired);
+
+        let (amount_to_transfer, remaining_points) = debit_points(
+            &mut ctx.accounts.reputation,
+            scoring,
+            amount,
+            ctx.accounts.arb_mint.decimals,
+            ctx.accounts.reward_vault.amount,
+        )?;
 
         // Transfer Tokens with PDA signer
         let bump = ctx.bumps.vault_authority;
         let seeds = &[b"vault_authority".as_ref(), &[bump]];
         let signer = &[&seeds[..]];
 
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.reward_vault.to_account_info(),
+            mint: ctx.accounts.arb_mint.to_account_info(),
             to: ctx.accounts.user_token_account.to_account_info(),
             authority: ctx.accounts.vault_authority.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-        token::transfer(cpi_ctx, amount_to_transfer)?;
+        token::transfer_checked(cpi_ctx, amount_to_transfer, ctx.accounts.arb_mint.decimals)?;
+
+        emit!(PointsRedeemed {
+            user: ctx.accounts.user.key(),
+            points: amount,
+            token_amount: amount_to_transfer,
+            remaining_points,
+        });
 
         Ok(())
     }
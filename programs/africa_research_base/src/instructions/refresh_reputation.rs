@@ -0,0 +1,41 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::events::ReputationUpdated;
+use crate::instructions::update_reputation::refresh_standing;
+use crate::{PauseCategory, ProgramConfig, Reputation};
+
+// Permissionless crank: re-applies decay so the stored standing reflects current inactivity
+#[derive(Accounts)]
+pub struct RefreshReputation<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(PauseCategory::Reputation) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"reputation", reputation.contributor.as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+}
+
+impl<'info> RefreshReputation<'info> {
+    // Only the standing and tier move; earned points and the redeemable balance are untouched
+    pub fn refresh_reputation(&mut self) -> Result<()> {
+        let reputation = &mut self.reputation;
+        refresh_standing(reputation, &self.config.scoring, Clock::get()?.unix_timestamp);
+
+        emit!(ReputationUpdated {
+            contributor: reputation.contributor,
+            action: "refresh".to_string(),
+            new_dataset_count: reputation.dataset_count,
+            new_reputation_score: reputation.reputation_score,
+        });
+        Ok(())
+    }
+}
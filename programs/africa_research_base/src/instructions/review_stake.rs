@@ -0,0 +1,373 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+
+use crate::error::ErrorCode;
+use crate::events::{ReviewSlashed, ReviewStakeChanged};
+use crate::instructions::roles::require_treasurer;
+use crate::instructions::update_reputation::calculate_reputation_score;
+use crate::{
+    Authority, Dataset, PauseCategory, ProgramConfig, Registry, Reputation, Review,
+    ReviewerStake, Role,
+};
+
+#[derive(Accounts)]
+pub struct InitializeReviewStakeVault<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [b"authority", registry.key().as_ref(), admin.key().as_ref()],
+        bump = admin_authority.bump
+    )]
+    pub admin_authority: Option<Account<'info, Authority>>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub arb_mint: Account<'info, Mint>,
+
+    // The vault is its own token authority
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"review_stake_vault"],
+        bump,
+        token::mint = arb_mint,
+        token::authority = review_stake_vault
+    )]
+    pub review_stake_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakeReview<'info> {
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(PauseCategory::Reputation) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"review_stake_vault"],
+        bump
+    )]
+    pub review_stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = config.arb_mint,
+        token::authority = reviewer
+    )]
+    pub reviewer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = reviewer,
+        space = 8 + ReviewerStake::INIT_SPACE,
+        seeds = [b"review_stake", reviewer.key().as_ref()],
+        bump
+    )]
+    pub reviewer_stake: Account<'info, ReviewerStake>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    pub reviewer: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"review_stake", reviewer.key().as_ref()],
+        bump = reviewer_stake.bump,
+        has_one = reviewer
+    )]
+    pub reviewer_stake: Account<'info, ReviewerStake>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    pub reviewer: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(PauseCategory::Reputation) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"review_stake_vault"],
+        bump
+    )]
+    pub review_stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = config.arb_mint,
+        token::authority = reviewer
+    )]
+    pub reviewer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"review_stake", reviewer.key().as_ref()],
+        bump = reviewer_stake.bump,
+        has_one = reviewer
+    )]
+    pub reviewer_stake: Account<'info, ReviewerStake>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SlashReview<'info> {
+    pub moderator: Signer<'info>,
+
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [b"authority", registry.key().as_ref(), moderator.key().as_ref()],
+        bump = moderator_authority.bump,
+        constraint = moderator_authority.has_role(Role::Moderator) @ ErrorCode::MissingRole
+    )]
+    pub moderator_authority: Account<'info, Authority>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        address = config.arb_mint
+    )]
+    pub arb_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"review_stake_vault"],
+        bump
+    )]
+    pub review_stake_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: The reviewed dataset, which may have been closed since. While it still exists the
+    /// slashed review is taken back out of its review totals
+    #[account(
+        mut,
+        address = review.dataset
+    )]
+    pub dataset: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"review", review.dataset.as_ref(), review.reviewer.as_ref()],
+        bump = review.bump,
+        constraint = !review.slashed @ ErrorCode::ReviewAlreadySlashed
+    )]
+    pub review: Account<'info, Review>,
+
+    #[account(
+        mut,
+        seeds = [b"review_stake", review.reviewer.as_ref()],
+        bump = reviewer_stake.bump
+    )]
+    pub reviewer_stake: Account<'info, ReviewerStake>,
+
+    #[account(
+        mut,
+        seeds = [b"reputation", review.reviewer.as_ref()],
+        bump = reviewer_reputation.bump
+    )]
+    pub reviewer_reputation: Account<'info, Reputation>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> InitializeReviewStakeVault<'info> {
+    pub fn initialize_review_stake_vault(&mut self) -> Result<()> {
+        require_treasurer(&self.registry, &self.admin.key(), &self.admin_authority)?;
+        require!(self.config.bind_arb_mint(self.arb_mint.key()), ErrorCode::InvalidMint);
+        Ok(())
+    }
+}
+
+impl<'info> StakeReview<'info> {
+    pub fn stake_review(&mut self, amount: u64, bumps: &StakeReviewBumps) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidStakeAmount);
+
+        let stake = &mut self.reviewer_stake;
+        stake.reviewer = self.reviewer.key();
+        stake.amount = stake
+            .amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        stake.bump = bumps.reviewer_stake;
+
+        let cpi_accounts = Transfer {
+            from: self.reviewer_token_account.to_account_info(),
+            to: self.review_stake_vault.to_account_info(),
+            authority: self.reviewer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        emit!(ReviewStakeChanged {
+            reviewer: stake.reviewer,
+            amount: stake.amount,
+            unbonding_amount: stake.unbonding_amount,
+            unbonding_until: stake.unbonding_until,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> RequestUnstake<'info> {
+    // Starts (or restarts) the unbonding clock for `amount` of active stake
+    pub fn request_unstake(&mut self, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let stake = &mut self.reviewer_stake;
+        require!(amount > 0, ErrorCode::InvalidStakeAmount);
+        require!(amount <= stake.unlocked(now), ErrorCode::StakeLocked);
+
+        stake.amount -= amount;
+        stake.unbonding_amount = stake
+            .unbonding_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        stake.unbonding_until = now
+            .checked_add(self.config.scoring.review_unbonding_period)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        emit!(ReviewStakeChanged {
+            reviewer: stake.reviewer,
+            amount: stake.amount,
+            unbonding_amount: stake.unbonding_amount,
+            unbonding_until: stake.unbonding_until,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> WithdrawStake<'info> {
+    pub fn withdraw_stake(&mut self, bumps: &WithdrawStakeBumps) -> Result<()> {
+        let stake = &mut self.reviewer_stake;
+        let amount = stake.unbonding_amount;
+        require!(amount > 0, ErrorCode::InvalidStakeAmount);
+        require!(
+            Clock::get()?.unix_timestamp >= stake.unbonding_until,
+            ErrorCode::StakeUnbonding
+        );
+        stake.unbonding_amount = 0;
+
+        let seeds = &[b"review_stake_vault".as_ref(), &[bumps.review_stake_vault]];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: self.review_stake_vault.to_account_info(),
+            to: self.reviewer_token_account.to_account_info(),
+            authority: self.review_stake_vault.to_account_info(),
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        emit!(ReviewStakeChanged {
+            reviewer: stake.reviewer,
+            amount: stake.amount,
+            unbonding_amount: 0,
+            unbonding_until: stake.unbonding_until,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> SlashReview<'info> {
+    // Burns up to `min_review_stake` of the reviewer's stake and voids the review
+    pub fn slash_review(&mut self, bumps: &SlashReviewBumps) -> Result<()> {
+        let scoring = &self.config.scoring;
+        let slashed = self.reviewer_stake.slash(scoring.min_review_stake);
+
+        if slashed > 0 {
+            let seeds = &[b"review_stake_vault".as_ref(), &[bumps.review_stake_vault]];
+            let signer = &[&seeds[..]];
+            let cpi_accounts = Burn {
+                mint: self.arb_mint.to_account_info(),
+                from: self.review_stake_vault.to_account_info(),
+                authority: self.review_stake_vault.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                signer,
+            );
+            token::burn(cpi_ctx, slashed)?;
+        }
+
+        let review = &mut self.review;
+        review.slashed = true;
+
+        // A closed dataset has no totals left to correct, and reviews of a superseded version
+        // were already dropped from them by update_dataset
+        let dataset_info = self.dataset.to_account_info();
+        if dataset_info.owner == &crate::ID && !dataset_info.data_is_empty() {
+            let mut dataset = Dataset::try_deserialize(&mut &dataset_info.try_borrow_data()?[..])?;
+            if review.dataset_version == dataset.version {
+                dataset.review_count = dataset.review_count.saturating_sub(1);
+                dataset.review_score_total = dataset
+                    .review_score_total
+                    .saturating_sub(review.rubric.score() as u64 * review.weight);
+                dataset.review_weight_total =
+                    dataset.review_weight_total.saturating_sub(review.weight);
+                dataset.try_serialize(&mut &mut dataset_info.try_borrow_mut_data()?[..])?;
+            }
+        }
+
+        // Claw back the reward if it was already paid, at the amount actually credited
+        if review.credited {
+            let reputation = &mut self.reviewer_reputation;
+            reputation.total_review_points = reputation
+                .total_review_points
+                .saturating_sub(review.reward_points as u64);
+            calculate_reputation_score(reputation, scoring, Clock::get()?.unix_timestamp)?;
+        }
+
+        emit!(ReviewSlashed {
+            review: review.key(),
+            reviewer: review.reviewer,
+            amount: slashed,
+            slashed_by: self.moderator.key(),
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::error::ErrorCode;
+use crate::events::RewardVaultFunded;
+use crate::instructions::roles::require_treasurer;
+use crate::{Authority, ProgramConfig, Registry};
+
+#[derive(Accounts)]
+pub struct InitializeRewardVault<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [b"authority", registry.key().as_ref(), admin.key().as_ref()],
+        bump = admin_authority.bump
+    )]
+    pub admin_authority: Option<Account<'info, Authority>>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.reward_vault == Pubkey::default() @ ErrorCode::RewardVaultAlreadyInitialized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub arb_mint: Account<'info, Mint>,
+
+    /// CHECK: PDA that signs transfers out of the reward vault
+    #[account(
+        seeds = [b"vault_authority"],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    // The vault authority's ATA, so a vault created off-chain before this instruction is adopted as-is
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = arb_mint,
+        associated_token::authority = vault_authority
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundRewardVault<'info> {
+    pub funder: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        address = config.reward_vault @ ErrorCode::InvalidRewardVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = config.arb_mint,
+        token::authority = funder
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> InitializeRewardVault<'info> {
+    pub fn initialize_reward_vault(&mut self) -> Result<()> {
+        require_treasurer(&self.registry, &self.admin.key(), &self.admin_authority)?;
+        require!(self.config.bind_arb_mint(self.arb_mint.key()), ErrorCode::InvalidMint);
+
+        self.config.reward_vault = self.reward_vault.key();
+        Ok(())
+    }
+}
+
+impl<'info> FundRewardVault<'info> {
+    // Anyone may top up the vault
+    pub fn fund_reward_vault(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidFundingAmount);
+
+        let cpi_accounts = Transfer {
+            from: self.funder_token_account.to_account_info(),
+            to: self.reward_vault.to_account_info(),
+            authority: self.funder.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        self.reward_vault.reload()?;
+        emit!(RewardVaultFunded {
+            funder: self.funder.key(),
+            amount,
+            vault_balance: self.reward_vault.amount,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::events::{RoleGranted, RoleRevoked};
+use crate::{Authority, Registry, Role};
+
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    // Only needed when the signer is a granted SuperAdmin rather than the registry admin
+    #[account(
+        seeds = [b"authority", registry.key().as_ref(), admin.key().as_ref()],
+        bump = admin_authority.bump
+    )]
+    pub admin_authority: Option<Account<'info, Authority>>,
+
+    /// CHECK: Only used as a seed for the holder's authority account
+    pub holder: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + Authority::INIT_SPACE,
+        seeds = [b"authority", registry.key().as_ref(), holder.key().as_ref()],
+        bump
+    )]
+    pub authority: Account<'info, Authority>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [b"authority", registry.key().as_ref(), admin.key().as_ref()],
+        bump = admin_authority.bump
+    )]
+    pub admin_authority: Option<Account<'info, Authority>>,
+
+    #[account(
+        mut,
+        seeds = [b"authority", registry.key().as_ref(), authority.holder.as_ref()],
+        bump = authority.bump
+    )]
+    pub authority: Account<'info, Authority>,
+}
+
+pub(crate) fn holds_role(signer_authority: &Option<Account<Authority>>, role: Role) -> bool {
+    signer_authority
+        .as_ref()
+        .is_some_and(|authority| authority.has_role(role))
+}
+
+// The registry admin is always a SuperAdmin; anyone else needs the role granted explicitly
+pub(crate) fn require_super_admin(
+    registry: &Registry,
+    signer: &Pubkey,
+    signer_authority: &Option<Account<Authority>>,
+) -> Result<()> {
+    let is_super_admin =
+        registry.admin == *signer || holds_role(signer_authority, Role::SuperAdmin);
+    require!(is_super_admin, ErrorCode::MissingRole);
+    Ok(())
+}
+
+// Token vaults are set up by a Treasurer; SuperAdmins (and so the registry admin) may too
+pub(crate) fn require_treasurer(
+    registry: &Registry,
+    signer: &Pubkey,
+    signer_authority: &Option<Account<Authority>>,
+) -> Result<()> {
+    if holds_role(signer_authority, Role::Treasurer) {
+        return Ok(());
+    }
+    require_super_admin(registry, signer, signer_authority)
+}
+
+impl<'info> GrantRole<'info> {
+    pub fn grant_role(&mut self, role: Role, bumps: &GrantRoleBumps) -> Result<()> {
+        require_super_admin(&self.registry, &self.admin.key(), &self.admin_authority)?;
+
+        let authority = &mut self.authority;
+        authority.registry = self.registry.key();
+        authority.holder = self.holder.key();
+        authority.roles |= role.mask();
+        authority.granted_by = self.admin.key();
+        authority.bump = bumps.authority;
+
+        emit!(RoleGranted {
+            holder: authority.holder,
+            role,
+            granted_by: self.admin.key(),
+        });
+        Ok(())
+    }
+}
+
+impl<'info> RevokeRole<'info> {
+    pub fn revoke_role(&mut self, role: Role) -> Result<()> {
+        require_super_admin(&self.registry, &self.admin.key(), &self.admin_authority)?;
+
+        let authority = &mut self.authority;
+        authority.roles &= !role.mask();
+
+        emit!(RoleRevoked {
+            holder: authority.holder,
+            role,
+            revoked_by: self.admin.key(),
+        });
+        Ok(())
+    }
+}
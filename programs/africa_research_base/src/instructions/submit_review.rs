@@ -0,0 +1,129 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::events::ReviewSubmitted;
+use crate::instructions::update_reputation::{current_standing, current_tier};
+use crate::{
+    Authority, ContributorTier, Dataset, PauseCategory, ProgramConfig, Registry, Reputation, Review, ReviewRubric,
+    ReviewVerdict, Role,
+};
+
+#[derive(Accounts)]
+pub struct SubmitReview<'info> {
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
+
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [b"authority", registry.key().as_ref(), reviewer.key().as_ref()],
+        bump = reviewer_authority.bump,
+        constraint = reviewer_authority.has_role(Role::Reviewer) @ ErrorCode::MissingRole
+    )]
+    pub reviewer_authority: Account<'info, Authority>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(PauseCategory::Reputation) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"dataset", dataset.contributor.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = dataset.is_active @ ErrorCode::DatasetInactive,
+        constraint = dataset.contributor != reviewer.key() @ ErrorCode::SelfReview
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        init,
+        payer = reviewer,
+        space = 8 + Review::INIT_SPACE,
+        seeds = [b"review", dataset.key().as_ref(), reviewer.key().as_ref()],
+        bump
+    )]
+    pub review: Account<'info, Review>,
+
+    // Weights the review; only Trusted contributors and above may review
+    #[account(
+        seeds = [b"reputation", reviewer.key().as_ref()],
+        bump = reviewer_reputation.bump
+    )]
+    pub reviewer_reputation: Account<'info, Reputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitReview<'info> {
+    pub fn submit_review(
+        &mut self,
+        verdict: ReviewVerdict,
+        rubric: ReviewRubric,
+        comment_hash: [u8; 32],
+        bumps: &SubmitReviewBumps,
+    ) -> Result<()> {
+        require!(rubric.is_valid(), ErrorCode::InvalidReview);
+
+        // Gate and weight on the standing decayed to now, not the last stored refresh
+        let created_at = Clock::get()?.unix_timestamp;
+        let scoring = &self.config.scoring;
+        require!(
+            current_tier(&self.reviewer_reputation, scoring, created_at) >= ContributorTier::Trusted,
+            ErrorCode::InsufficientTier
+        );
+
+        let score = rubric.score();
+        // +1 so reviewers without any reputation yet still count
+        let weight = current_standing(&self.reviewer_reputation, scoring, created_at)
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        let dataset = &mut self.dataset;
+        dataset.review_count = dataset
+            .review_count
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        dataset.review_score_total = (score as u64)
+            .checked_mul(weight)
+            .and_then(|weighted| dataset.review_score_total.checked_add(weighted))
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        dataset.review_weight_total = dataset
+            .review_weight_total
+            .checked_add(weight)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        self.review.set_inner(Review {
+            dataset: self.dataset.key(),
+            dataset_version: self.dataset.version,
+            reviewer: self.reviewer.key(),
+            contributor: self.dataset.contributor,
+            verdict,
+            rubric,
+            comment_hash,
+            weight,
+            created_at,
+            credited: false,
+            reward_points: 0,
+            slashed: false,
+            bump: bumps.review,
+        });
+
+        emit!(ReviewSubmitted {
+            dataset: self.dataset.key(),
+            reviewer: self.reviewer.key(),
+            verdict,
+            score,
+            created_at,
+        });
+
+        Ok(())
+    }
+}
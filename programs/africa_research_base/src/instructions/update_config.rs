@@ -0,0 +1,85 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::events::ConfigUpdated;
+use crate::instructions::roles::require_super_admin;
+use crate::{Authority, ProgramConfig, Registry, ScoringParams};
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ProgramConfig::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [b"authority", registry.key().as_ref(), admin.key().as_ref()],
+        bump = admin_authority.bump
+    )]
+    pub admin_authority: Option<Account<'info, Authority>>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+impl<'info> InitializeConfig<'info> {
+    pub fn initialize_config(&mut self, bumps: &InitializeConfigBumps) -> Result<()> {
+        self.config.set_inner(ProgramConfig {
+            scoring: ScoringParams::default(),
+            paused: false,
+            paused_categories: 0,
+            arb_mint: Pubkey::default(),
+            reward_vault: Pubkey::default(),
+            bump: bumps.config,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn update_config(&mut self, scoring: ScoringParams) -> Result<()> {
+        require_super_admin(&self.registry, &self.admin.key(), &self.admin_authority)?;
+        require!(scoring.is_valid(), ErrorCode::InvalidConfig);
+
+        self.config.scoring = scoring;
+
+        emit!(ConfigUpdated {
+            updated_by: self.admin.key(),
+            scoring,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+
+use crate::constants::MAX_FILE_SIZE;
+use crate::error::ErrorCode;
+use crate::events::DatasetUpdated;
+use crate::instructions::create_dataset::{quality_attestation_message, verify_quality_attestation};
+use crate::instructions::update_reputation::{
+    calculate_reputation_score, current_tier, rescore_upload_points,
+};
+use crate::{
+    AttestationNonce, Authority, ContentHashIndex, ContributorTier, Dataset, DatasetVersion,
+    PauseCategory, ProgramConfig, Registry, Reputation, Role,
+};
+
+#[derive(Accounts)]
+#[instruction(
+    content_hash: [u8; 32],
+    ai_metadata: Vec<u8>,
+    data_uri: [u8; 256],
+    column_count: u64,
+    row_count: u64,
+    file_size: u64,
+    quality_score: u8,
+    nonce: u64
+)]
+pub struct UpdateDataset<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(PauseCategory::Datasets) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"dataset", dataset.contributor.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        has_one = contributor @ ErrorCode::UnauthorizedUpdate,
+        constraint = dataset.is_active && !dataset.moderated @ ErrorCode::DatasetInactive
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        init,
+        payer = contributor,
+        space = 8 + DatasetVersion::INIT_SPACE,
+        seeds = [b"dataset_version", dataset.key().as_ref(), &dataset.version.to_le_bytes()],
+        bump
+    )]
+    pub dataset_version: Account<'info, DatasetVersion>,
+
+    // Indexes of superseded hashes stay pointed at this dataset so older versions remain unique
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = 8 + ContentHashIndex::INIT_SPACE,
+        seeds = [b"hash", content_hash.as_ref()],
+        bump
+    )]
+    pub hash_index: Account<'info, ContentHashIndex>,
+
+    #[account(
+        mut,
+        seeds = [b"reputation", contributor.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    /// CHECK: Key of the oracle that signed the quality attestation; its role is checked below
+    pub quality_oracle: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"authority", registry.key().as_ref(), quality_oracle.key().as_ref()],
+        bump = oracle_authority.bump,
+        constraint = oracle_authority.has_role(Role::QualityOracle) @ ErrorCode::MissingRole
+    )]
+    pub oracle_authority: Account<'info, Authority>,
+
+    #[account(
+        init,
+        payer = contributor,
+        space = 8 + AttestationNonce::INIT_SPACE,
+        seeds = [b"nonce", quality_oracle.key().as_ref(), &nonce.to_le_bytes()],
+        bump
+    )]
+    pub attestation_nonce: Account<'info, AttestationNonce>,
+
+    /// CHECK: Instructions sysvar, read to find the Ed25519 verification instruction
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateDataset<'info> {
+    // Every revision carries new contents, so it needs its own quality attestation and the
+    // upload reward is rescored from it
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_dataset(
+        &mut self,
+        content_hash: [u8; 32],
+        ai_metadata: Vec<u8>,
+        data_uri: [u8; 256],
+        column_count: u64,
+        row_count: u64,
+        file_size: u64,
+        quality_score: u8,
+        nonce: u64,
+        bumps: &UpdateDatasetBumps,
+    ) -> Result<()> {
+        require!(quality_score <= 100, ErrorCode::InvalidQualityScore);
+        require!(file_size <= MAX_FILE_SIZE, ErrorCode::FileTooLarge);
+        require!(file_size > 0, ErrorCode::InvalidFileSize);
+        // A revision may not grow past what the contributor's current tier allows to upload
+        let clock = Clock::get()?;
+        require!(
+            file_size <= self.config.scoring.trusted_upload_size
+                || current_tier(&self.reputation, &self.config.scoring, clock.unix_timestamp)
+                    >= ContributorTier::Trusted,
+            ErrorCode::InsufficientTier
+        );
+        require!(column_count <= 100, ErrorCode::TooManyColumns);
+
+        let message = quality_attestation_message(
+            &self.contributor.key(),
+            &content_hash,
+            quality_score,
+            row_count,
+            column_count,
+            file_size,
+            nonce,
+        );
+        verify_quality_attestation(&self.instructions, &self.quality_oracle.key(), &message)?;
+
+        let dataset_key = self.dataset.key();
+        self.attestation_nonce.set_inner(AttestationNonce {
+            oracle: self.quality_oracle.key(),
+            nonce,
+            dataset: dataset_key,
+            bump: bumps.attestation_nonce,
+        });
+
+        require!(
+            self.hash_index.claim(content_hash, dataset_key, self.contributor.key(), bumps.hash_index),
+            ErrorCode::DuplicateDataset
+        );
+
+        let dataset = &mut self.dataset;
+
+        // Archive the current contents under the current version number
+        self.dataset_version.set_inner(DatasetVersion {
+            dataset: dataset.key(),
+            version: dataset.version,
+            content_hash: dataset.content_hash,
+            ai_metadata: std::mem::take(&mut dataset.ai_metadata),
+            data_uri: dataset.data_uri,
+            column_count: dataset.column_count,
+            row_count: dataset.row_count,
+            file_size: dataset.file_size,
+            published_at: dataset.last_updated.unwrap_or(dataset.upload_timestamp),
+            superseded_at: clock.unix_timestamp,
+            bump: bumps.dataset_version,
+        });
+
+        let reputation = &mut self.reputation;
+        reputation.total_quality_score = reputation
+            .total_quality_score
+            .checked_sub(dataset.quality_score as u64)
+            .ok_or(ErrorCode::NumericalOverflow)?
+            .checked_add(quality_score as u64)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        dataset.content_hash = content_hash;
+        dataset.ai_metadata = ai_metadata;
+        dataset.data_uri = data_uri;
+        dataset.column_count = column_count;
+        dataset.row_count = row_count;
+        dataset.file_size = file_size;
+        dataset.quality_score = quality_score;
+        dataset.last_updated = Some(clock.unix_timestamp);
+        // Reviews judged the previous contents, so the new version starts without any
+        dataset.review_count = 0;
+        dataset.review_score_total = 0;
+        dataset.review_weight_total = 0;
+        dataset.version = dataset
+            .version
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        let scoring = &self.config.scoring;
+        rescore_upload_points(reputation, dataset, scoring)?;
+        calculate_reputation_score(reputation, scoring, clock.unix_timestamp)?;
+
+        emit!(DatasetUpdated {
+            id: dataset.key(),
+            contributor: dataset.contributor,
+            version: dataset.version,
+            content_hash,
+            quality_score,
+            updated_at: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
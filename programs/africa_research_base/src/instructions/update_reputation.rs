@@ -1,35 +1,40 @@
 #![allow(unexpected_cfgs)]
 use anchor_lang::prelude::*;
 
-use crate::constants::*;
 use crate::error::ErrorCode;
-use crate::{Dataset, Reputation};
+use crate::events::TierChanged;
+use crate::{
+    Authority, ContributorTier, Dataset, PauseCategory, ProgramConfig, Registry, Reputation,
+    Review, ReviewerStake, Role, ScoringParams,
+};
 
 #[derive(Accounts)]
 pub struct UpdateReputationOnUpload<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
-
-    #[account(mut)]
-    pub contributor: Signer<'info>,
+    pub oracle: Signer<'info>,
 
     #[account(
-        mut,
-        seeds = [b"reputation", contributor.key().as_ref()],
-        bump = reputation.bump
+        seeds = [b"registry"],
+        bump = registry.bump
     )]
-    pub reputation: Account<'info, Reputation>,
+    pub registry: Account<'info, Registry>,
 
-    pub system_program: Program<'info, System>,
-}
+    #[account(
+        seeds = [b"authority", registry.key().as_ref(), oracle.key().as_ref()],
+        bump = oracle_authority.bump,
+        constraint = oracle_authority.has_role(Role::ReputationOracle) @ ErrorCode::UnauthorizedReputationUpdate
+    )]
+    pub oracle_authority: Account<'info, Authority>,
 
-#[derive(Accounts)]
-pub struct UpdateReputationOnDownload<'info> {
-    #[account(mut)]
-    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(PauseCategory::Reputation) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
 
-    #[account(mut)]
-    pub contributor: Signer<'info>,
+    /// CHECK: Only used as a seed for the contributor's accounts
+    pub contributor: UncheckedAccount<'info>,
 
     #[account(
         mut,
@@ -38,47 +43,40 @@ pub struct UpdateReputationOnDownload<'info> {
     )]
     pub reputation: Account<'info, Reputation>,
 
+    // Re-scoring is bound to a dataset; upload points are first awarded by `create_dataset`
     #[account(
         mut,
         seeds = [b"dataset", contributor.key().as_ref(), &dataset.dataset_index.to_le_bytes()],
-        bump,
-        constraint = dataset.contributor == contributor.key()
+        bump = dataset.bump,
+        has_one = contributor
     )]
     pub dataset: Account<'info, Dataset>,
-
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateReputationOnCitation<'info> {
-    #[account(mut)]
-    pub admin: Signer<'info>,
-
+pub struct UpdateReputationOnReview<'info> {
     #[account(mut)]
-    pub contributor: Signer<'info>,
+    pub oracle: Signer<'info>,
 
     #[account(
-        mut,
-        seeds = [b"reputation", contributor.key().as_ref()],
-        bump = reputation.bump
+        seeds = [b"registry"],
+        bump = registry.bump
     )]
-    pub reputation: Account<'info, Reputation>,
+    pub registry: Account<'info, Registry>,
 
     #[account(
-        mut,
-        seeds = [b"dataset", contributor.key().as_ref(), &dataset.dataset_index.to_le_bytes()],
-        bump,
-        constraint = dataset.contributor == contributor.key()
+        seeds = [b"authority", registry.key().as_ref(), oracle.key().as_ref()],
+        bump = oracle_authority.bump,
+        constraint = oracle_authority.has_role(Role::ReputationOracle) @ ErrorCode::UnauthorizedReputationUpdate
     )]
-    pub dataset: Account<'info, Dataset>,
-
-    pub system_program: Program<'info, System>,
-}
+    pub oracle_authority: Account<'info, Authority>,
 
-#[derive(Accounts)]
-pub struct UpdateReputationOnReview<'info> {
-    #[account(mut)]
-    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(PauseCategory::Reputation) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
 
     #[account(mut)]
     pub contributor: Signer<'info>, // The reviewer
@@ -90,15 +88,58 @@ pub struct UpdateReputationOnReview<'info> {
     )]
     pub reputation: Account<'info, Reputation>,
 
-    // Reviews are typically on a dataset, but for simplicity of points, we might not enforced constraint here unless we track specific reviews.
-    // For now, minimal context to award points.
+    #[account(
+        seeds = [b"authority", registry.key().as_ref(), contributor.key().as_ref()],
+        bump = reviewer_authority.bump,
+        constraint = reviewer_authority.has_role(Role::Reviewer) @ ErrorCode::MissingRole
+    )]
+    pub reviewer_authority: Account<'info, Authority>,
+
+    // The reward is paid once per review, i.e. once per (dataset, reviewer)
+    #[account(
+        mut,
+        seeds = [b"review", review.dataset.as_ref(), contributor.key().as_ref()],
+        bump = review.bump,
+        constraint = !review.credited @ ErrorCode::ReviewAlreadyCredited,
+        constraint = !review.slashed @ ErrorCode::ReviewAlreadySlashed
+    )]
+    pub review: Account<'info, Review>,
+
+    // Rewards are only paid to reviewers with enough unlocked ARB at stake
+    #[account(
+        mut,
+        seeds = [b"review_stake", contributor.key().as_ref()],
+        bump = reviewer_stake.bump
+    )]
+    pub reviewer_stake: Account<'info, ReviewerStake>,
+
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct UpdateReputationActivity<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub oracle: Signer<'info>,
+
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [b"authority", registry.key().as_ref(), oracle.key().as_ref()],
+        bump = oracle_authority.bump,
+        constraint = oracle_authority.has_role(Role::ReputationOracle) @ ErrorCode::UnauthorizedReputationUpdate
+    )]
+    pub oracle_authority: Account<'info, Authority>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(PauseCategory::Reputation) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
 
     #[account(mut)]
     pub contributor: Signer<'info>,
@@ -113,95 +154,177 @@ pub struct UpdateReputationActivity<'info> {
     pub system_program: Program<'info, System>,
 }
 
-// Standalone helper for reputation score calculation
-fn calculate_reputation_score(reputation: &mut Reputation) -> Result<()> {
-    // New Formula: Sum of accumulated points + (downloads * weight) + (citations * weight)
-    // Uploads/Reviews/Activity are now accumulated in their own fields.
-    // Downloads/Citations still use fixed weights for now as requested.
+// Replaces the tier reward previously credited for `dataset` with the one its quality score earns now
+pub(crate) fn rescore_upload_points(
+    reputation: &mut Reputation,
+    dataset: &mut Dataset,
+    scoring: &ScoringParams,
+) -> Result<()> {
+    let reward = scoring.upload_reward(dataset.quality_score);
 
-    let download_score = (reputation.total_downloads as u64)
-        .checked_mul(DOWNLOAD_WEIGHT as u64)
+    reputation.total_upload_points = reputation
+        .total_upload_points
+        .checked_sub(dataset.upload_points as u64)
+        .ok_or(ErrorCode::NumericalOverflow)?
+        .checked_add(reward as u64)
         .ok_or(ErrorCode::NumericalOverflow)?;
+    dataset.upload_points = reward;
 
-    let citation_score = (reputation.total_citations as u64)
-        .checked_mul(CITATION_WEIGHT as u64)
+    Ok(())
+}
+
+// Takes a closed dataset's upload, quality score and upload reward back out of its
+// contributor's reputation; `dataset_count` is the next seed index, so it is never decremented
+pub(crate) fn uncredit_dataset(reputation: &mut Reputation, dataset: &Dataset) -> Result<()> {
+    reputation.total_uploads = reputation
+        .total_uploads
+        .checked_sub(1)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+    reputation.total_quality_score = reputation
+        .total_quality_score
+        .checked_sub(dataset.quality_score as u64)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+    reputation.total_upload_points = reputation
+        .total_upload_points
+        .checked_sub(dataset.upload_points as u64)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+    Ok(())
+}
+
+pub(crate) fn credit_download(
+    reputation: &mut Reputation,
+    scoring: &ScoringParams,
+    download_time: i64,
+) -> Result<()> {
+    reputation.total_downloads = reputation
+        .total_downloads
+        .checked_add(1)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+    reputation.total_download_points = reputation
+        .total_download_points
+        .checked_add(scoring.download_weight as u64)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+    reputation.download_time = download_time;
+    Ok(())
+}
+
+pub(crate) fn credit_citation(reputation: &mut Reputation, scoring: &ScoringParams) -> Result<()> {
+    reputation.total_citations = reputation
+        .total_citations
+        .checked_add(1)
         .ok_or(ErrorCode::NumericalOverflow)?;
+    reputation.total_citation_points = reputation
+        .total_citation_points
+        .checked_add(scoring.citation_weight as u64)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+    Ok(())
+}
 
+// Marks the contributor active at `now`, starting a new daily activity allowance on a new day
+pub(crate) fn touch_activity(reputation: &mut Reputation, now: i64) {
+    // Simple day check: timestamp / 86400 (seconds in a day)
+    if now / 86400 > reputation.last_activity_timestamp / 86400 {
+        reputation.daily_activity_points = 0;
+    }
+    reputation.last_activity_timestamp = now;
+}
+
+// Standalone helper for reputation score calculation: sums the accumulators into the earned
+// points, re-derives the redeemable balance from them and refreshes the standing
+pub(crate) fn calculate_reputation_score(
+    reputation: &mut Reputation,
+    scoring: &ScoringParams,
+    now: i64,
+) -> Result<()> {
+    // Downloads and citations are accumulated already weighted, so a weight change in config
+    // only prices those credited after it
     let total_points = reputation
         .total_upload_points
         .checked_add(reputation.total_review_points)
         .ok_or(ErrorCode::NumericalOverflow)?
         .checked_add(reputation.total_activity_points)
         .ok_or(ErrorCode::NumericalOverflow)?
-        .checked_add(download_score)
+        .checked_add(reputation.total_download_points)
         .ok_or(ErrorCode::NumericalOverflow)?
-        .checked_add(citation_score)
+        .checked_add(reputation.total_citation_points)
         .ok_or(ErrorCode::NumericalOverflow)?;
 
-    reputation.reputation_score = total_points as u32; // Cast to u32 as per struct definition. Might overflow if points get huge. User's system seems to expect u32.
+    // The spendable balance follows the earned points, not the decayed standing. Claimed points
+    // stay claimed: a loss (closed dataset, lower tier, slashed review) larger than the unspent
+    // balance has to be earned back before anything becomes redeemable again
+    reputation.earned_points = total_points;
+    reputation.redeemable_points = total_points.saturating_sub(reputation.claimed_points);
 
+    refresh_standing(reputation, scoring, now);
     Ok(())
 }
 
-impl<'info> UpdateReputationOnUpload<'info> {
-    pub fn update_reputation_upload(&mut self, quality_score: u8) -> Result<()> {
-        let reputation = &mut self.reputation;
-        reputation.total_uploads = reputation
-            .total_uploads
-            .checked_add(1)
-            .ok_or(ErrorCode::NumericalOverflow)?;
-        reputation.total_quality_score = reputation
-            .total_quality_score
-            .checked_add(quality_score as u64)
-            .ok_or(ErrorCode::NumericalOverflow)?;
-
-        // Tiered Reward Logic
-        let reward: u32 = if quality_score > UPLOAD_TIER_2_THRESHOLD {
-            UPLOAD_TIER_3_REWARD // 300
-        } else if quality_score >= UPLOAD_TIER_1_THRESHOLD {
-            UPLOAD_TIER_2_REWARD // 35
-        } else {
-            UPLOAD_TIER_1_REWARD // 20
-        };
-
-        reputation.total_upload_points = reputation
-            .total_upload_points
-            .checked_add(reward as u64)
-            .ok_or(ErrorCode::NumericalOverflow)?;
-
-        calculate_reputation_score(reputation)?;
-        Ok(())
+// Standing as of `now`, whether or not the stored score has been refreshed since; decay is
+// applied lazily, from the time since `last_activity_timestamp`
+pub(crate) fn current_standing(reputation: &Reputation, scoring: &ScoringParams, now: i64) -> u64 {
+    // Accounts that predate activity tracking have no timestamp and are left undecayed
+    // until their first activity
+    if reputation.last_activity_timestamp > 0 {
+        scoring.decayed_score(
+            reputation.earned_points,
+            now.saturating_sub(reputation.last_activity_timestamp),
+        )
+    } else {
+        reputation.earned_points
     }
 }
 
-impl<'info> UpdateReputationOnDownload<'info> {
-    pub fn update_reputation_download(&mut self) -> Result<()> {
-        let reputation = &mut self.reputation;
-        reputation.total_downloads = reputation
-            .total_downloads
-            .checked_add(1)
-            .ok_or(ErrorCode::NumericalOverflow)?;
-        let clock = Clock::get()?;
-        reputation.download_time = clock.unix_timestamp;
-        calculate_reputation_score(reputation)?;
-        Ok(())
+// Tier gates use this rather than the stored tier, which lags until the next refresh
+pub(crate) fn current_tier(
+    reputation: &Reputation,
+    scoring: &ScoringParams,
+    now: i64,
+) -> ContributorTier {
+    scoring.tier_for(current_standing(reputation, scoring, now))
+}
+
+// Re-derives the stored standing and tier from the stored earned points
+pub(crate) fn refresh_standing(reputation: &mut Reputation, scoring: &ScoringParams, now: i64) {
+    reputation.reputation_score = current_standing(reputation, scoring, now);
+
+    let tier = scoring.tier_for(reputation.reputation_score);
+    if tier != reputation.tier {
+        emit!(TierChanged {
+            contributor: reputation.contributor,
+            previous_tier: reputation.tier,
+            new_tier: tier,
+            reputation_score: reputation.reputation_score,
+        });
+        reputation.tier = tier;
     }
 }
 
-impl<'info> UpdateReputationOnCitation<'info> {
-    pub fn update_reputation_citation(&mut self) -> Result<()> {
+impl<'info> UpdateReputationOnUpload<'info> {
+    // Re-applies the current upload tiers to the dataset's quality score, e.g. after a config change
+    pub fn update_reputation_upload(&mut self) -> Result<()> {
+        let scoring = &self.config.scoring;
         let reputation = &mut self.reputation;
-        reputation.total_citations = reputation
-            .total_citations
-            .checked_add(1)
-            .ok_or(ErrorCode::NumericalOverflow)?;
-        calculate_reputation_score(reputation)?;
+
+        rescore_upload_points(reputation, &mut self.dataset, scoring)?;
+        calculate_reputation_score(reputation, scoring, Clock::get()?.unix_timestamp)?;
         Ok(())
     }
 }
 
 impl<'info> UpdateReputationOnReview<'info> {
     pub fn update_reputation_review(&mut self) -> Result<()> {
+        let scoring = &self.config.scoring;
+        let now = Clock::get()?.unix_timestamp;
+
+        // Each credited review keeps `min_review_stake` locked while it can still be slashed
+        let lock_until = now
+            .checked_add(scoring.review_unbonding_period)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        require!(
+            self.reviewer_stake.lock(scoring.min_review_stake, lock_until, now),
+            ErrorCode::InsufficientStake
+        );
+
         let reputation = &mut self.reputation;
 
         reputation.total_reviews = reputation
@@ -210,35 +333,31 @@ impl<'info> UpdateReputationOnReview<'info> {
             .ok_or(ErrorCode::NumericalOverflow)?;
         reputation.total_review_points = reputation
             .total_review_points
-            .checked_add(REVIEW_REWARD as u64)
+            .checked_add(scoring.review_reward as u64)
             .ok_or(ErrorCode::NumericalOverflow)?;
+        self.review.credited = true;
+        self.review.reward_points = scoring.review_reward;
 
-        calculate_reputation_score(reputation)?;
+        calculate_reputation_score(reputation, scoring, now)?;
         Ok(())
     }
 }
 
 impl<'info> UpdateReputationActivity<'info> {
     pub fn update_reputation_activity(&mut self) -> Result<()> {
+        let scoring = &self.config.scoring;
         let reputation = &mut self.reputation;
         let clock = Clock::get()?;
         let current_time = clock.unix_timestamp;
 
         // Daily Reset Logic
-        // Simple day check: timestamp / 86400 (seconds in a day)
-        let current_day = current_time / 86400;
-        let last_activity_day = reputation.last_activity_timestamp / 86400;
-
-        if current_day > last_activity_day {
-            reputation.daily_activity_points = 0;
-        }
-
-        reputation.last_activity_timestamp = current_time;
+        touch_activity(reputation, current_time);
 
         // Apply Points if under cap
-        if reputation.daily_activity_points < DAILY_COMMUNITY_CAP {
-            let points_to_add =
-                COMMUNITY_REWARD.min(DAILY_COMMUNITY_CAP - reputation.daily_activity_points);
+        if reputation.daily_activity_points < scoring.daily_community_cap {
+            let points_to_add = scoring
+                .community_reward
+                .min(scoring.daily_community_cap - reputation.daily_activity_points);
 
             if points_to_add > 0 {
                 reputation.daily_activity_points = reputation
@@ -252,7 +371,294 @@ impl<'info> UpdateReputationActivity<'info> {
             }
         }
 
-        calculate_reputation_score(reputation)?;
+        calculate_reputation_score(reputation, scoring, current_time)?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{
+        CONTRIBUTOR_TIER_THRESHOLD, CURATOR_TIER_THRESHOLD, DOWNLOAD_WEIGHT, SECONDS_PER_YEAR,
+        TRUSTED_TIER_THRESHOLD,
+    };
+
+    fn reputation() -> Reputation {
+        Reputation {
+            account_version: Reputation::ACCOUNT_VERSION,
+            contributor: Pubkey::default(),
+            total_uploads: 0,
+            dataset_count: 0,
+            download_time: 0,
+            total_quality_score: 0,
+            total_downloads: 0,
+            total_citations: 0,
+            reputation_score: 0,
+            earned_points: 0,
+            tier: ContributorTier::Novice,
+            total_reviews: 0,
+            last_activity_timestamp: 0,
+            daily_activity_points: 0,
+            total_upload_points: 0,
+            total_review_points: 0,
+            total_activity_points: 0,
+            total_download_points: 0,
+            total_citation_points: 0,
+            legacy_upload_points: 0,
+            claimed_points: 0,
+            redeemable_points: 0,
+            vesting_count: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn score_keeps_points_past_u32_max() {
+        let mut reputation = reputation();
+        reputation.total_upload_points = u32::MAX as u64;
+        reputation.total_activity_points = 1;
+
+        calculate_reputation_score(&mut reputation, &ScoringParams::default(), 0).unwrap();
+        assert_eq!(reputation.reputation_score, u32::MAX as u64 + 1);
+        assert_eq!(reputation.redeemable_points, u32::MAX as u64 + 1);
+    }
+
+    #[test]
+    fn score_at_u64_max_is_accepted() {
+        let mut reputation = reputation();
+        reputation.total_upload_points = u64::MAX - 1;
+        reputation.total_review_points = 1;
+
+        calculate_reputation_score(&mut reputation, &ScoringParams::default(), 0).unwrap();
+        assert_eq!(reputation.reputation_score, u64::MAX);
+    }
+
+    #[test]
+    fn score_past_u64_max_is_rejected() {
+        let mut reputation = reputation();
+        reputation.total_upload_points = u64::MAX;
+        reputation.total_review_points = 1;
+
+        let result = calculate_reputation_score(&mut reputation, &ScoringParams::default(), 0);
+        assert_eq!(result.unwrap_err(), ErrorCode::NumericalOverflow.into());
+        assert_eq!(reputation.reputation_score, 0);
+    }
+
+    #[test]
+    fn weighted_downloads_overflow_is_rejected() {
+        let mut reputation = reputation();
+        reputation.total_download_points = u64::MAX - DOWNLOAD_WEIGHT as u64 + 1;
+
+        let result = credit_download(&mut reputation, &ScoringParams::default(), 0);
+        assert_eq!(result.unwrap_err(), ErrorCode::NumericalOverflow.into());
+    }
+
+    #[test]
+    fn weight_changes_do_not_reprice_earned_points() {
+        let mut reputation = reputation();
+        let scoring = ScoringParams::default();
+        credit_download(&mut reputation, &scoring, 0).unwrap();
+        credit_citation(&mut reputation, &scoring).unwrap();
+        calculate_reputation_score(&mut reputation, &scoring, 0).unwrap();
+        let earned = reputation.earned_points;
+        assert_eq!(earned, (scoring.download_weight + scoring.citation_weight) as u64);
+
+        let reweighted = ScoringParams {
+            download_weight: scoring.download_weight * 3,
+            citation_weight: scoring.citation_weight * 3,
+            ..scoring
+        };
+        calculate_reputation_score(&mut reputation, &reweighted, 0).unwrap();
+        assert_eq!(reputation.earned_points, earned);
+        assert_eq!(reputation.redeemable_points, earned);
+
+        // Only later credits use the new weight
+        credit_download(&mut reputation, &reweighted, 0).unwrap();
+        calculate_reputation_score(&mut reputation, &reweighted, 0).unwrap();
+        assert_eq!(reputation.earned_points, earned + reweighted.download_weight as u64);
+    }
+
+    #[test]
+    fn refreshing_the_standing_leaves_points_alone() {
+        let mut reputation = reputation();
+        let scoring = decaying();
+        reputation.total_upload_points = TRUSTED_TIER_THRESHOLD;
+        reputation.last_activity_timestamp = 1_000_000;
+        calculate_reputation_score(&mut reputation, &scoring, 1_000_000).unwrap();
+
+        // Accumulators that disagree with the earned points are not re-summed
+        reputation.total_upload_points = 0;
+        refresh_standing(&mut reputation, &scoring, 1_000_000 + 86_400);
+        assert_eq!(reputation.earned_points, TRUSTED_TIER_THRESHOLD);
+        assert_eq!(reputation.redeemable_points, TRUSTED_TIER_THRESHOLD);
+        assert_eq!(reputation.reputation_score, TRUSTED_TIER_THRESHOLD / 2);
+        assert_eq!(reputation.tier, ContributorTier::Contributor);
+    }
+
+    #[test]
+    fn redeemable_points_follow_score_changes() {
+        let mut reputation = reputation();
+        let scoring = ScoringParams::default();
+        reputation.total_upload_points = 300;
+        calculate_reputation_score(&mut reputation, &scoring, 0).unwrap();
+
+        // Redeeming spends the balance without touching the score
+        reputation.redeemable_points -= 250;
+        reputation.claimed_points += 250;
+        reputation.total_upload_points = 400;
+        calculate_reputation_score(&mut reputation, &scoring, 0).unwrap();
+        assert_eq!(reputation.reputation_score, 400);
+        assert_eq!(reputation.redeemable_points, 150);
+    }
+
+    #[test]
+    fn lost_points_are_not_credited_twice() {
+        let mut reputation = reputation();
+        let scoring = ScoringParams::default();
+        reputation.total_upload_points = 300;
+        calculate_reputation_score(&mut reputation, &scoring, 0).unwrap();
+
+        // Redeem everything, then lose the upload (closed or downgraded dataset)
+        reputation.redeemable_points -= 300;
+        reputation.claimed_points += 300;
+        reputation.total_upload_points = 100;
+        calculate_reputation_score(&mut reputation, &scoring, 0).unwrap();
+        assert_eq!(reputation.redeemable_points, 0);
+
+        // Earning the lost points back only repays what was already claimed
+        reputation.total_upload_points = 300;
+        calculate_reputation_score(&mut reputation, &scoring, 0).unwrap();
+        assert_eq!(reputation.redeemable_points, 0);
+
+        reputation.total_upload_points = 350;
+        calculate_reputation_score(&mut reputation, &scoring, 0).unwrap();
+        assert_eq!(reputation.redeemable_points, 50);
+    }
+
+    fn decaying() -> ScoringParams {
+        ScoringParams {
+            decay_enabled: true,
+            decay_period: 86_400,
+            decay_rate_bps: 5_000,
+            ..ScoringParams::default()
+        }
+    }
+
+    #[test]
+    fn decay_is_off_by_default() {
+        let mut reputation = reputation();
+        reputation.total_upload_points = 300;
+        reputation.last_activity_timestamp = 1;
+
+        calculate_reputation_score(&mut reputation, &ScoringParams::default(), 10 * SECONDS_PER_YEAR)
+            .unwrap();
+        assert_eq!(reputation.reputation_score, 300);
+    }
+
+    #[test]
+    fn decay_steps_per_full_idle_period() {
+        let mut reputation = reputation();
+        let scoring = decaying();
+        reputation.total_upload_points = 800;
+        reputation.last_activity_timestamp = 1_000_000;
+
+        // Not yet a full period
+        calculate_reputation_score(&mut reputation, &scoring, 1_000_000 + 86_399).unwrap();
+        assert_eq!(reputation.reputation_score, 800);
+
+        calculate_reputation_score(&mut reputation, &scoring, 1_000_000 + 86_400).unwrap();
+        assert_eq!(reputation.reputation_score, 400);
+
+        calculate_reputation_score(&mut reputation, &scoring, 1_000_000 + 3 * 86_400 + 5).unwrap();
+        assert_eq!(reputation.reputation_score, 100);
+
+        // Activity restores the full standing
+        touch_activity(&mut reputation, 1_000_000 + 3 * 86_400 + 5);
+        calculate_reputation_score(&mut reputation, &scoring, 1_000_000 + 3 * 86_400 + 5).unwrap();
+        assert_eq!(reputation.reputation_score, 800);
+    }
+
+    #[test]
+    fn decay_leaves_redeemable_points_alone() {
+        let mut reputation = reputation();
+        let scoring = decaying();
+        reputation.total_upload_points = 800;
+        reputation.last_activity_timestamp = 1_000_000;
+        calculate_reputation_score(&mut reputation, &scoring, 1_000_000).unwrap();
+
+        calculate_reputation_score(&mut reputation, &scoring, 1_000_000 + 2 * 86_400).unwrap();
+        assert_eq!(reputation.reputation_score, 200);
+        assert_eq!(reputation.earned_points, 800);
+        assert_eq!(reputation.redeemable_points, 800);
+    }
+
+    #[test]
+    fn decay_over_many_periods_bottoms_out_at_zero() {
+        let mut reputation = reputation();
+        reputation.total_upload_points = u64::MAX;
+        reputation.last_activity_timestamp = 1;
+
+        calculate_reputation_score(&mut reputation, &decaying(), i64::MAX).unwrap();
+        assert_eq!(reputation.reputation_score, 0);
+        assert_eq!(reputation.earned_points, u64::MAX);
+    }
+
+    #[test]
+    fn accounts_without_activity_timestamp_are_not_decayed() {
+        let mut reputation = reputation();
+        reputation.total_upload_points = 300;
+
+        calculate_reputation_score(&mut reputation, &decaying(), 10 * SECONDS_PER_YEAR).unwrap();
+        assert_eq!(reputation.reputation_score, 300);
+    }
+
+    #[test]
+    fn tier_follows_standing_score() {
+        let mut reputation = reputation();
+        let scoring = ScoringParams::default();
+
+        reputation.total_upload_points = CONTRIBUTOR_TIER_THRESHOLD - 1;
+        calculate_reputation_score(&mut reputation, &scoring, 0).unwrap();
+        assert_eq!(reputation.tier, ContributorTier::Novice);
+
+        reputation.total_upload_points = CONTRIBUTOR_TIER_THRESHOLD;
+        calculate_reputation_score(&mut reputation, &scoring, 0).unwrap();
+        assert_eq!(reputation.tier, ContributorTier::Contributor);
+
+        reputation.total_upload_points = TRUSTED_TIER_THRESHOLD;
+        calculate_reputation_score(&mut reputation, &scoring, 0).unwrap();
+        assert_eq!(reputation.tier, ContributorTier::Trusted);
+
+        reputation.total_upload_points = CURATOR_TIER_THRESHOLD;
+        calculate_reputation_score(&mut reputation, &scoring, 0).unwrap();
+        assert_eq!(reputation.tier, ContributorTier::Curator);
+    }
+
+    #[test]
+    fn gates_see_decay_before_the_stored_tier_is_refreshed() {
+        let mut reputation = reputation();
+        let scoring = decaying();
+        reputation.total_upload_points = TRUSTED_TIER_THRESHOLD;
+        reputation.last_activity_timestamp = 1_000_000;
+        calculate_reputation_score(&mut reputation, &scoring, 1_000_000).unwrap();
+
+        let later = 1_000_000 + 86_400;
+        assert_eq!(reputation.tier, ContributorTier::Trusted);
+        assert_eq!(current_standing(&reputation, &scoring, later), TRUSTED_TIER_THRESHOLD / 2);
+        assert_eq!(current_tier(&reputation, &scoring, later), ContributorTier::Contributor);
+    }
+
+    #[test]
+    fn decay_can_lower_the_tier() {
+        let mut reputation = reputation();
+        let scoring = decaying();
+        reputation.total_upload_points = TRUSTED_TIER_THRESHOLD;
+        reputation.last_activity_timestamp = 1_000_000;
+        calculate_reputation_score(&mut reputation, &scoring, 1_000_000).unwrap();
+        assert_eq!(reputation.tier, ContributorTier::Trusted);
+
+        calculate_reputation_score(&mut reputation, &scoring, 1_000_000 + 86_400).unwrap();
+        assert_eq!(reputation.tier, ContributorTier::Contributor);
+    }
+}
@@ -0,0 +1,228 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::error::ErrorCode;
+use crate::events::{PointsRedeemed, VestedTokensClaimed, VestingScheduleCreated};
+use crate::instructions::redeem::debit_points;
+use crate::{PauseCategory, ProgramConfig, Reputation, VestingSchedule};
+
+#[derive(Accounts)]
+pub struct RedeemPointsVested<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"reputation", user.key().as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.contributor == user.key()
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(PauseCategory::Redemption) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        address = config.arb_mint @ ErrorCode::InvalidMint
+    )]
+    pub arb_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = config.reward_vault @ ErrorCode::InvalidRewardVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: The authority allowed to transfer from the vault
+    #[account(
+        seeds = [b"vault_authority"],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + VestingSchedule::INIT_SPACE,
+        seeds = [b"vesting", user.key().as_ref(), &reputation.vesting_count.to_le_bytes()],
+        bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    // Escrow for the unclaimed tokens of all the user's schedules; its own token authority
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"vesting_escrow", user.key().as_ref()],
+        bump,
+        token::mint = arb_mint,
+        token::authority = vesting_escrow
+    )]
+    pub vesting_escrow: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(PauseCategory::Redemption) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        address = config.arb_mint @ ErrorCode::InvalidMint
+    )]
+    pub arb_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting", user.key().as_ref(), &vesting_schedule.index.to_le_bytes()],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.beneficiary == user.key()
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting_escrow", user.key().as_ref()],
+        bump
+    )]
+    pub vesting_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = arb_mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RedeemPointsVested<'info> {
+    // Redeems `amount` points into the user's vesting schedule instead of their wallet
+    pub fn redeem_points_vested(&mut self, amount: u64, bumps: &RedeemPointsVestedBumps) -> Result<()> {
+        let scoring = &self.config.scoring;
+        require!(scoring.vesting_enabled, ErrorCode::VestingDisabled);
+
+        let (token_amount, remaining_points) = debit_points(
+            &mut self.reputation,
+            scoring,
+            amount,
+            self.arb_mint.decimals,
+            self.reward_vault.amount,
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let reputation = &mut self.reputation;
+        let index = reputation.vesting_count;
+        reputation.vesting_count = index
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        let schedule = &mut self.vesting_schedule;
+        schedule.set_inner(VestingSchedule {
+            beneficiary: self.user.key(),
+            index,
+            total_amount: token_amount,
+            claimed_amount: 0,
+            start_time: now,
+            cliff_time: now
+                .checked_add(scoring.vesting_cliff)
+                .ok_or(ErrorCode::NumericalOverflow)?,
+            end_time: now
+                .checked_add(scoring.vesting_duration)
+                .ok_or(ErrorCode::NumericalOverflow)?,
+            bump: bumps.vesting_schedule,
+        });
+
+        let seeds = &[b"vault_authority".as_ref(), &[bumps.vault_authority]];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = TransferChecked {
+            from: self.reward_vault.to_account_info(),
+            mint: self.arb_mint.to_account_info(),
+            to: self.vesting_escrow.to_account_info(),
+            authority: self.vault_authority.to_account_info(),
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer);
+        token::transfer_checked(cpi_ctx, token_amount, self.arb_mint.decimals)?;
+
+        emit!(PointsRedeemed {
+            user: self.user.key(),
+            points: amount,
+            token_amount,
+            remaining_points,
+        });
+        emit!(VestingScheduleCreated {
+            beneficiary: schedule.beneficiary,
+            schedule: schedule.key(),
+            amount: token_amount,
+            locked_amount: schedule.total_amount,
+            cliff_time: schedule.cliff_time,
+            end_time: schedule.end_time,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> ClaimVested<'info> {
+    pub fn claim_vested(&mut self, bumps: &ClaimVestedBumps) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let schedule = &mut self.vesting_schedule;
+        let amount = schedule.claimable_amount(now);
+        require!(amount > 0, ErrorCode::NothingVested);
+
+        schedule.claimed_amount = schedule
+            .claimed_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        let user_key = self.user.key();
+        let seeds = &[
+            b"vesting_escrow".as_ref(),
+            user_key.as_ref(),
+            &[bumps.vesting_escrow],
+        ];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = TransferChecked {
+            from: self.vesting_escrow.to_account_info(),
+            mint: self.arb_mint.to_account_info(),
+            to: self.user_token_account.to_account_info(),
+            authority: self.vesting_escrow.to_account_info(),
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer);
+        token::transfer_checked(cpi_ctx, amount, self.arb_mint.decimals)?;
+
+        emit!(VestedTokensClaimed {
+            beneficiary: schedule.beneficiary,
+            schedule: schedule.key(),
+            amount,
+            claimed_amount: schedule.claimed_amount,
+            locked_amount: schedule.locked_amount(now),
+        });
+
+        // A fully claimed schedule has nothing left to track; return its rent
+        if schedule.claimed_amount == schedule.total_amount {
+            self.vesting_schedule.close(self.user.to_account_info())?;
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
 #![allow(unexpected_cfgs)]
+// Anchor's generated IDL handlers still call the deprecated `AccountInfo::realloc`.
+#![allow(deprecated)]
 pub mod constants;
 pub mod error;
 pub mod events;
@@ -21,6 +23,7 @@ pub mod africa_research_base {
         ctx.accounts.initialize_registry(&ctx.bumps)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_dataset(
         ctx: Context<CreateDataset>,
         content_hash: [u8; 32],
@@ -31,6 +34,7 @@ pub mod africa_research_base {
         column_count: u64,
         row_count: u64,
         quality_score: u8,
+        nonce: u64,
         // upload_timestamp: i64,
         // last_updated: Option<i64>,
         // download_count: u32,
@@ -45,28 +49,107 @@ pub mod africa_research_base {
             column_count,
             row_count,
             quality_score,
+            nonce,
             &ctx.bumps,
         )?;
 
         Ok(())
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_dataset(
+        ctx: Context<UpdateDataset>,
+        content_hash: [u8; 32],
+        ai_metadata: Vec<u8>,
+        data_uri: [u8; 256],
+        column_count: u64,
+        row_count: u64,
+        file_size: u64,
+        quality_score: u8,
+        nonce: u64,
+    ) -> Result<()> {
+        ctx.accounts.update_dataset(
+            content_hash,
+            ai_metadata,
+            data_uri,
+            column_count,
+            row_count,
+            file_size,
+            quality_score,
+            nonce,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn deactivate_dataset(ctx: Context<SetDatasetStatus>) -> Result<()> {
+        ctx.accounts.set_dataset_status(false)
+    }
+
+    pub fn reactivate_dataset(ctx: Context<SetDatasetStatus>) -> Result<()> {
+        ctx.accounts.set_dataset_status(true)
+    }
+
+    pub fn close_dataset<'info>(ctx: Context<'_, '_, 'info, 'info, CloseDataset<'info>>) -> Result<()> {
+        ctx.accounts.close_dataset(ctx.remaining_accounts)
+    }
+
     pub fn initialize_reputation(ctx: Context<InitializeReputation>) -> Result<()> {
         ctx.accounts.initialize_reputation(&ctx.bumps)
     }
 
-    pub fn update_reputation_upload(
-        ctx: Context<UpdateReputationOnUpload>,
-        quality_score: u8,
+    pub fn update_reputation_upload(ctx: Context<UpdateReputationOnUpload>) -> Result<()> {
+        ctx.accounts.update_reputation_upload()
+    }
+
+    pub fn record_download(ctx: Context<RecordDownload>) -> Result<()> {
+        ctx.accounts.record_download(&ctx.bumps)
+    }
+
+    pub fn record_citation(
+        ctx: Context<RecordCitation>,
+        publication_hash: [u8; 32],
+        reference: PublicationReference,
+    ) -> Result<()> {
+        ctx.accounts
+            .record_citation(publication_hash, reference, &ctx.bumps)
+    }
+
+    pub fn submit_review(
+        ctx: Context<SubmitReview>,
+        verdict: ReviewVerdict,
+        rubric: ReviewRubric,
+        comment_hash: [u8; 32],
     ) -> Result<()> {
-        ctx.accounts.update_reputation_upload(quality_score)
+        ctx.accounts
+            .submit_review(verdict, rubric, comment_hash, &ctx.bumps)
+    }
+
+    pub fn recalculate_quality_score(ctx: Context<RecalculateQualityScore>) -> Result<()> {
+        ctx.accounts.recalculate_quality_score()
+    }
+
+    pub fn refresh_reputation(ctx: Context<RefreshReputation>) -> Result<()> {
+        ctx.accounts.refresh_reputation()
     }
 
-    pub fn update_reputation_download(ctx: Context<UpdateReputationOnDownload>) -> Result<()> {
-        ctx.accounts.update_reputation_download()
+    pub fn initialize_review_stake_vault(ctx: Context<InitializeReviewStakeVault>) -> Result<()> {
+        ctx.accounts.initialize_review_stake_vault()
     }
 
-    pub fn update_reputation_citation(ctx: Context<UpdateReputationOnCitation>) -> Result<()> {
-        ctx.accounts.update_reputation_citation()
+    pub fn stake_review(ctx: Context<StakeReview>, amount: u64) -> Result<()> {
+        ctx.accounts.stake_review(amount, &ctx.bumps)
+    }
+
+    pub fn request_unstake(ctx: Context<RequestUnstake>, amount: u64) -> Result<()> {
+        ctx.accounts.request_unstake(amount)
+    }
+
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>) -> Result<()> {
+        ctx.accounts.withdraw_stake(&ctx.bumps)
+    }
+
+    pub fn slash_review(ctx: Context<SlashReview>) -> Result<()> {
+        ctx.accounts.slash_review(&ctx.bumps)
     }
 
     pub fn update_reputation_review(ctx: Context<UpdateReputationOnReview>) -> Result<()> {
@@ -77,7 +160,67 @@ pub mod africa_research_base {
         ctx.accounts.update_reputation_activity()
     }
 
-    pub fn redeem_points(ctx: Context<RedeemPoints>) -> Result<()> {
-        RedeemPoints::redeem_points(ctx)
+    pub fn initialize_reward_vault(ctx: Context<InitializeRewardVault>) -> Result<()> {
+        ctx.accounts.initialize_reward_vault()
+    }
+
+    pub fn fund_reward_vault(ctx: Context<FundRewardVault>, amount: u64) -> Result<()> {
+        ctx.accounts.fund_reward_vault(amount)
+    }
+
+    pub fn redeem_points(ctx: Context<RedeemPoints>, amount: u64) -> Result<()> {
+        RedeemPoints::redeem_points(ctx, amount)
+    }
+
+    pub fn redeem_points_vested(ctx: Context<RedeemPointsVested>, amount: u64) -> Result<()> {
+        ctx.accounts.redeem_points_vested(amount, &ctx.bumps)
+    }
+
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        ctx.accounts.claim_vested(&ctx.bumps)
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role) -> Result<()> {
+        ctx.accounts.grant_role(role, &ctx.bumps)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>, role: Role) -> Result<()> {
+        ctx.accounts.revoke_role(role)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.propose_admin(new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.accept_admin()
+    }
+
+    pub fn migrate_registry(ctx: Context<MigrateRegistry>) -> Result<()> {
+        ctx.accounts.migrate_registry(&ctx.bumps)
+    }
+
+    pub fn migrate_reputation(ctx: Context<MigrateReputation>) -> Result<()> {
+        ctx.accounts.migrate_reputation()
+    }
+
+    pub fn migrate_dataset(ctx: Context<MigrateDataset>) -> Result<()> {
+        ctx.accounts.migrate_dataset()
+    }
+
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        ctx.accounts.initialize_config(&ctx.bumps)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, scoring: ScoringParams) -> Result<()> {
+        ctx.accounts.update_config(scoring)
+    }
+
+    pub fn pause(ctx: Context<SetPause>, category: Option<PauseCategory>) -> Result<()> {
+        ctx.accounts.set_pause(category, true)
+    }
+
+    pub fn unpause(ctx: Context<SetPause>, category: Option<PauseCategory>) -> Result<()> {
+        ctx.accounts.set_pause(category, false)
     }
 }
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+// Marks a quality-oracle nonce as spent so an attestation cannot be replayed
+#[account]
+#[derive(InitSpace)]
+pub struct AttestationNonce {
+    pub oracle: Pubkey,
+    pub nonce: u64,
+    pub dataset: Pubkey,
+    pub bump: u8
+}
@@ -3,9 +3,14 @@ use anchor_lang::prelude::*;
 #[account]
 #[derive(InitSpace)]
 pub struct Attribution {
+    pub account_version: u8,
     pub dataset_id: Pubkey,
     pub downloader: Pubkey,
     pub contributor: Pubkey,
     pub download_time: i64,
     pub bump: u8
-}
\ No newline at end of file
+}
+
+impl Attribution {
+    pub const ACCOUNT_VERSION: u8 = 1;
+}
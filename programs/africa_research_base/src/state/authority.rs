@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    SuperAdmin,
+    ReputationOracle,
+    Reviewer,
+    Moderator,
+    Treasurer,
+    QualityOracle,
+}
+
+impl Role {
+    pub fn mask(self) -> u8 {
+        1 << (self as u8)
+    }
+}
+
+// One account per (registry, holder); roles are stored as a bitmask of `Role::mask()`.
+#[account]
+#[derive(InitSpace)]
+pub struct Authority {
+    pub registry: Pubkey,
+    pub holder: Pubkey,
+    pub roles: u8,
+    pub granted_by: Pubkey,
+    pub bump: u8,
+}
+
+impl Authority {
+    pub fn has_role(&self, role: Role) -> bool {
+        self.roles & role.mask() != 0
+    }
+}
@@ -1,14 +1,279 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::constants::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PublicationKind {
+    Doi,   // e.g. 10.1000/xyz123
+    Arxiv, // e.g. 2401.01234v2 or hep-th/9901001
+    Isbn,  // ISBN-10 or ISBN-13, hyphens allowed
+    Url,   // http(s) link for anything without a persistent identifier
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct PublicationReference {
+    pub kind: PublicationKind,
+
+    #[max_len(256)]
+    pub identifier: String,
+    pub title_hash: [u8; 32],
+    pub year: u16,
+
+    #[max_len(128)]
+    pub venue: String,
+}
+
+impl PublicationReference {
+    pub fn is_valid(&self, current_year: u16) -> bool {
+        let identifier_ok = match self.kind {
+            PublicationKind::Doi => is_valid_doi(&self.identifier),
+            PublicationKind::Arxiv => is_valid_arxiv_id(&self.identifier),
+            PublicationKind::Isbn => is_valid_isbn(&self.identifier),
+            PublicationKind::Url => is_valid_url(&self.identifier),
+        };
+
+        identifier_ok
+            && self.identifier.len() <= MAX_PUBLICATION_IDENTIFIER_LEN
+            && self.venue.len() <= MAX_PUBLICATION_VENUE_LEN
+            && self.title_hash != [0u8; 32]
+            && self.year >= MIN_PUBLICATION_YEAR
+            && self.year <= current_year
+    }
+
+    // Dedup key: kind plus the normalized identifier, so the same paper hashes the same
+    // regardless of DOI casing, ISBN hyphenation or length, arXiv revision, URL spelling or the
+    // title/venue strings supplied
+    pub fn publication_hash(&self) -> [u8; 32] {
+        let identifier: String = match self.kind {
+            PublicationKind::Doi => self.identifier.to_ascii_lowercase(),
+            PublicationKind::Isbn => canonical_isbn(&self.identifier),
+            PublicationKind::Arxiv => canonical_arxiv_id(&self.identifier),
+            PublicationKind::Url => canonical_url(&self.identifier),
+        };
+        hashv(&[&[self.kind as u8], identifier.as_bytes()]).to_bytes()
+    }
+}
+
+// 10.<registrant>/<suffix>, registrant made of digits and dots
+fn is_valid_doi(doi: &str) -> bool {
+    let Some((prefix, suffix)) = doi.split_once('/') else {
+        return false;
+    };
+    let Some(registrant) = prefix.strip_prefix("10.") else {
+        return false;
+    };
+
+    registrant.len() >= 4
+        && registrant.split('.').all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+        && !suffix.is_empty()
+        && !doi.bytes().any(|b| b.is_ascii_whitespace() || b.is_ascii_control())
+}
+
+// New style YYMM.NNNN(N) or old style archive/YYMMNNN, each with an optional vN suffix
+fn is_valid_arxiv_id(id: &str) -> bool {
+    let base = match id.rsplit_once('v') {
+        Some((base, version))
+            if !version.is_empty() && version.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            base
+        }
+        _ => id,
+    };
+
+    if let Some((archive, number)) = base.split_once('/') {
+        return !archive.is_empty()
+            && archive.bytes().all(|b| b.is_ascii_alphabetic() || b == b'-' || b == b'.')
+            && number.len() == 7
+            && number.bytes().all(|b| b.is_ascii_digit());
+    }
+
+    match base.split_once('.') {
+        Some((yymm, number)) => {
+            yymm.len() == 4
+                && yymm.bytes().all(|b| b.is_ascii_digit())
+                && (number.len() == 4 || number.len() == 5)
+                && number.bytes().all(|b| b.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+// Archive names are case-insensitive and every vN revision is the same paper
+fn canonical_arxiv_id(id: &str) -> String {
+    let id = id.to_ascii_lowercase();
+    match id.rsplit_once('v') {
+        Some((base, version))
+            if !version.is_empty() && version.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            base.to_string()
+        }
+        _ => id,
+    }
+}
+
+// Scheme, `www.`, host case, fragment and trailing slashes do not change which page is cited.
+// Paths and queries are case-sensitive, so they are kept as given
+fn canonical_url(url: &str) -> String {
+    let scheme_len = ["https://", "http://"]
+        .iter()
+        .find(|scheme| {
+            url.get(..scheme.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+        })
+        .map_or(0, |scheme| scheme.len());
+    let rest = &url[scheme_len..];
+    let rest = rest.split('#').next().unwrap_or_default();
+
+    let host_end = rest.find(['/', '?']).unwrap_or(rest.len());
+    let (host, path) = rest.split_at(host_end);
+    let host = host.to_ascii_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+
+    format!("{host}{}", path.trim_end_matches(['/', '?']))
+}
+
+// ISBN-10s are hashed as their ISBN-13 (978 prefix, EAN check digit) so both forms of a book match
+fn canonical_isbn(isbn: &str) -> String {
+    let digits: String = isbn
+        .chars()
+        .filter(|c| *c != '-' && *c != ' ')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if digits.len() != 10 {
+        return digits;
+    }
+
+    let mut isbn13 = format!("978{}", &digits[..9]);
+    let sum: u32 = isbn13
+        .bytes()
+        .enumerate()
+        .map(|(i, b)| (b - b'0') as u32 * if i % 2 == 0 { 1 } else { 3 })
+        .sum();
+    isbn13.push(char::from(b'0' + ((10 - sum % 10) % 10) as u8));
+    isbn13
+}
+
+// Checks the ISBN-10 or ISBN-13 check digit; hyphens and spaces are ignored
+// (`is_multiple_of` is newer than the SBF toolchain's rustc)
+#[allow(clippy::manual_is_multiple_of)]
+fn is_valid_isbn(isbn: &str) -> bool {
+    let digits: Vec<u8> = isbn.bytes().filter(|b| *b != b'-' && *b != b' ').collect();
+
+    match digits.len() {
+        10 => {
+            let mut sum = 0u32;
+            for (i, b) in digits.iter().enumerate() {
+                let value = match b {
+                    b'0'..=b'9' => (b - b'0') as u32,
+                    b'X' | b'x' if i == 9 => 10,
+                    _ => return false,
+                };
+                sum += value * (10 - i as u32);
+            }
+            sum % 11 == 0
+        }
+        13 => {
+            if !digits.iter().all(|b| b.is_ascii_digit()) {
+                return false;
+            }
+            let sum: u32 = digits
+                .iter()
+                .enumerate()
+                .map(|(i, b)| (b - b'0') as u32 * if i % 2 == 0 { 1 } else { 3 })
+                .sum();
+            sum % 10 == 0
+        }
+        _ => false,
+    }
+}
+
+fn is_valid_url(url: &str) -> bool {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"));
+
+    match rest {
+        Some(rest) => {
+            let host = rest.split('/').next().unwrap_or_default();
+            host.contains('.') && !url.bytes().any(|b| b.is_ascii_whitespace() || b.is_ascii_control())
+        }
+        None => false,
+    }
+}
 
 #[account]
 #[derive(InitSpace)]
 pub struct Citation {
+    pub account_version: u8,
     pub dataset_id: Pubkey,
     pub citer: Pubkey,
     pub contributor: Pubkey,
-    
-    #[max_len(1000)]
-    pub published_information: Vec<u8>,
+    pub publication_hash: [u8; 32],
+    pub reference: PublicationReference,
     pub citing_time: i64,
     pub bump: u8
-}
\ No newline at end of file
+}
+
+impl Citation {
+    pub const ACCOUNT_VERSION: u8 = 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(kind: PublicationKind, identifier: &str) -> PublicationReference {
+        PublicationReference {
+            kind,
+            identifier: identifier.to_string(),
+            title_hash: [1u8; 32],
+            year: 2024,
+            venue: String::new(),
+        }
+    }
+
+    fn same_publication(kind: PublicationKind, a: &str, b: &str) -> bool {
+        reference(kind, a).publication_hash() == reference(kind, b).publication_hash()
+    }
+
+    #[test]
+    fn arxiv_revisions_are_one_publication() {
+        assert!(same_publication(PublicationKind::Arxiv, "2401.01234", "2401.01234v2"));
+        assert!(same_publication(PublicationKind::Arxiv, "hep-th/9901001v1", "HEP-TH/9901001"));
+        assert!(!same_publication(PublicationKind::Arxiv, "2401.01234", "2401.01235"));
+    }
+
+    #[test]
+    fn url_spellings_are_one_publication() {
+        let canonical = "https://example.org/papers/sahel";
+        for variant in [
+            "http://example.org/papers/sahel",
+            "https://www.example.org/papers/sahel",
+            "https://Example.ORG/papers/sahel/",
+            "HTTPS://WWW.example.org/papers/sahel",
+            "https://example.org/papers/sahel#abstract",
+            "https://example.org/papers/sahel/?",
+        ] {
+            assert!(same_publication(PublicationKind::Url, canonical, variant), "{variant}");
+        }
+        assert!(!same_publication(PublicationKind::Url, canonical, "https://example.org/papers/sahel?id=2"));
+        assert!(!same_publication(PublicationKind::Url, canonical, "https://example.org/papers/sahel-2"));
+        assert!(!same_publication(PublicationKind::Url, canonical, "https://example.org/Papers/Sahel"));
+        assert!(!same_publication(PublicationKind::Url, canonical, "https://example.org/papers/sahel?id=A"));
+    }
+
+    #[test]
+    fn isbn_10_and_13_are_one_publication() {
+        assert!(same_publication(PublicationKind::Isbn, "0-306-40615-2", "978-0-306-40615-7"));
+        assert!(same_publication(PublicationKind::Isbn, "0-8044-2957-X", "9780804429573"));
+        assert!(!same_publication(PublicationKind::Isbn, "0-306-40615-2", "979-0-306-40615-4"));
+    }
+
+    #[test]
+    fn kinds_do_not_collide() {
+        let doi = reference(PublicationKind::Doi, "10.1000/xyz").publication_hash();
+        let url = reference(PublicationKind::Url, "10.1000/xyz").publication_hash();
+        assert_ne!(doi, url);
+    }
+}
@@ -0,0 +1,216 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::ContributorTier;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct ScoringParams {
+    pub download_weight: u32,
+    pub citation_weight: u32,
+    pub review_reward: u32,
+    pub community_reward: u32,
+    pub daily_community_cap: u32,
+
+    // Tiered upload thresholds and rewards
+    pub upload_tier_1_threshold: u8,
+    pub upload_tier_2_threshold: u8,
+    pub upload_tier_1_reward: u32,
+    pub upload_tier_2_reward: u32,
+    pub upload_tier_3_reward: u32,
+
+    // Whole ARB per point = numerator / denominator
+    pub points_to_arb_numerator: u64,
+    pub points_to_arb_denominator: u64,
+    // Bounds on the points a single redemption may claim
+    pub min_redeem_points: u64,
+    pub max_redeem_points: u64,
+
+    // When enabled, redemptions are escrowed and vest linearly after a cliff
+    pub vesting_enabled: bool,
+    pub vesting_cliff: i64,
+    pub vesting_duration: i64,
+
+    // When enabled, the standing score loses `decay_rate_bps` for every full
+    // `decay_period` since the contributor's last activity
+    pub decay_enabled: bool,
+    pub decay_period: i64,
+    pub decay_rate_bps: u16,
+
+    // Standing needed for each tier above Novice, and the upload size that needs Trusted
+    pub contributor_tier_threshold: u64,
+    pub trusted_tier_threshold: u64,
+    pub curator_tier_threshold: u64,
+    pub trusted_upload_size: u64,
+
+    // Review staking: stake needed before review rewards are paid, and the withdrawal delay
+    pub min_review_stake: u64,
+    pub review_unbonding_period: i64,
+}
+
+impl Default for ScoringParams {
+    fn default() -> Self {
+        Self {
+            download_weight: DOWNLOAD_WEIGHT,
+            citation_weight: CITATION_WEIGHT,
+            review_reward: REVIEW_REWARD,
+            community_reward: COMMUNITY_REWARD,
+            daily_community_cap: DAILY_COMMUNITY_CAP,
+            upload_tier_1_threshold: UPLOAD_TIER_1_THRESHOLD,
+            upload_tier_2_threshold: UPLOAD_TIER_2_THRESHOLD,
+            upload_tier_1_reward: UPLOAD_TIER_1_REWARD,
+            upload_tier_2_reward: UPLOAD_TIER_2_REWARD,
+            upload_tier_3_reward: UPLOAD_TIER_3_REWARD,
+            points_to_arb_numerator: POINTS_TO_ARB_NUMERATOR,
+            points_to_arb_denominator: POINTS_TO_ARB_DENOMINATOR,
+            min_redeem_points: MIN_REDEEM_POINTS,
+            max_redeem_points: MAX_REDEEM_POINTS,
+            vesting_enabled: false,
+            vesting_cliff: VESTING_CLIFF,
+            vesting_duration: VESTING_DURATION,
+            decay_enabled: false,
+            decay_period: DECAY_PERIOD,
+            decay_rate_bps: DECAY_RATE_BPS,
+            contributor_tier_threshold: CONTRIBUTOR_TIER_THRESHOLD,
+            trusted_tier_threshold: TRUSTED_TIER_THRESHOLD,
+            curator_tier_threshold: CURATOR_TIER_THRESHOLD,
+            trusted_upload_size: TRUSTED_UPLOAD_SIZE,
+            min_review_stake: MIN_REVIEW_STAKE,
+            review_unbonding_period: REVIEW_UNBONDING_PERIOD,
+        }
+    }
+}
+
+impl ScoringParams {
+    pub fn is_valid(&self) -> bool {
+        self.upload_tier_1_threshold < self.upload_tier_2_threshold
+            && self.upload_tier_2_threshold <= 100
+            && self.upload_tier_1_reward <= self.upload_tier_2_reward
+            && self.upload_tier_2_reward <= self.upload_tier_3_reward
+            && self.upload_tier_3_reward <= MAX_POINT_REWARD
+            && self.review_reward <= MAX_POINT_REWARD
+            && self.download_weight <= MAX_POINT_REWARD
+            && self.citation_weight <= MAX_POINT_REWARD
+            && self.daily_community_cap > 0
+            && self.daily_community_cap <= MAX_POINT_REWARD
+            && self.community_reward <= self.daily_community_cap
+            && self.points_to_arb_numerator > 0
+            && self.points_to_arb_denominator > 0
+            && self.min_redeem_points > 0
+            && self.min_redeem_points <= self.max_redeem_points
+            && self.vesting_cliff >= 0
+            && self.vesting_cliff <= self.vesting_duration
+            && self.vesting_duration > 0
+            && self.vesting_duration <= MAX_VESTING_DURATION
+            && self.decay_period >= MIN_DECAY_PERIOD
+            && self.decay_period <= MAX_DECAY_PERIOD
+            && self.decay_rate_bps as u64 <= BPS_DENOMINATOR
+            && self.contributor_tier_threshold > 0
+            && self.contributor_tier_threshold < self.trusted_tier_threshold
+            && self.trusted_tier_threshold < self.curator_tier_threshold
+            && self.trusted_upload_size > 0
+            && self.trusted_upload_size <= MAX_FILE_SIZE
+            && self.min_review_stake > 0
+            && self.review_unbonding_period >= 0
+            && self.review_unbonding_period <= MAX_REVIEW_UNBONDING_PERIOD
+    }
+
+    // Token base units owed for `points` on a mint with `decimals`; rounds down
+    pub fn points_to_tokens(&self, points: u64, decimals: u8) -> Option<u64> {
+        let scale = 10u128.checked_pow(decimals as u32)?;
+        let amount = (points as u128)
+            .checked_mul(self.points_to_arb_numerator as u128)?
+            .checked_mul(scale)?
+            / self.points_to_arb_denominator as u128;
+        u64::try_from(amount).ok()
+    }
+
+    // Standing left from `points` after `idle_for` seconds without activity; rounds down
+    pub fn decayed_score(&self, points: u64, idle_for: i64) -> u64 {
+        if !self.decay_enabled || idle_for < self.decay_period {
+            return points;
+        }
+        let periods = (idle_for / self.decay_period) as u64;
+        let retained = bps_pow(BPS_DENOMINATOR - self.decay_rate_bps as u64, periods);
+        (points as u128 * retained as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    pub fn tier_for(&self, reputation_score: u64) -> ContributorTier {
+        if reputation_score >= self.curator_tier_threshold {
+            ContributorTier::Curator
+        } else if reputation_score >= self.trusted_tier_threshold {
+            ContributorTier::Trusted
+        } else if reputation_score >= self.contributor_tier_threshold {
+            ContributorTier::Contributor
+        } else {
+            ContributorTier::Novice
+        }
+    }
+
+    pub fn upload_reward(&self, quality_score: u8) -> u32 {
+        if quality_score > self.upload_tier_2_threshold {
+            self.upload_tier_3_reward
+        } else if quality_score >= self.upload_tier_1_threshold {
+            self.upload_tier_2_reward
+        } else {
+            self.upload_tier_1_reward
+        }
+    }
+}
+
+// (base / BPS_DENOMINATOR)^exponent in basis points, by squaring; each step rounds down
+fn bps_pow(base: u64, mut exponent: u64) -> u64 {
+    let mut result = BPS_DENOMINATOR;
+    let mut base = base;
+    while exponent > 0 && result > 0 {
+        if exponent & 1 == 1 {
+            result = result * base / BPS_DENOMINATOR;
+        }
+        base = base * base / BPS_DENOMINATOR;
+        exponent >>= 1;
+    }
+    result
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PauseCategory {
+    Datasets,
+    Reputation,
+    Redemption,
+}
+
+impl PauseCategory {
+    pub fn mask(self) -> u8 {
+        1 << (self as u8)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramConfig {
+    pub scoring: ScoringParams,
+
+    // Circuit breaker: `paused` stops everything, `paused_categories` is a bitmask of `PauseCategory::mask()`
+    pub paused: bool,
+    pub paused_categories: u8,
+
+    // ARB token mint; unset until the first token vault is initialized
+    pub arb_mint: Pubkey,
+    // Token account redemptions are paid from; set by `initialize_reward_vault`
+    pub reward_vault: Pubkey,
+
+    pub bump: u8,
+}
+
+impl ProgramConfig {
+    // Records the ARB mint on first use; every later vault must use the same mint
+    pub fn bind_arb_mint(&mut self, mint: Pubkey) -> bool {
+        if self.arb_mint == Pubkey::default() {
+            self.arb_mint = mint;
+        }
+        self.arb_mint == mint
+    }
+
+    pub fn is_paused(&self, category: PauseCategory) -> bool {
+        self.paused || self.paused_categories & category.mask() != 0
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+// Global `[b"hash", content_hash]` lookup from file contents to the dataset that registered them
+#[account]
+#[derive(InitSpace)]
+pub struct ContentHashIndex {
+    pub content_hash: [u8; 32],
+    pub dataset: Pubkey,
+    pub contributor: Pubkey,
+    pub bump: u8
+}
+
+impl ContentHashIndex {
+    // Points the index at `dataset`; returns false when another dataset already holds this hash
+    pub fn claim(
+        &mut self,
+        content_hash: [u8; 32],
+        dataset: Pubkey,
+        contributor: Pubkey,
+        bump: u8,
+    ) -> bool {
+        if self.dataset != Pubkey::default() && self.dataset != dataset {
+            return false;
+        }
+        self.content_hash = content_hash;
+        self.dataset = dataset;
+        self.contributor = contributor;
+        self.bump = bump;
+        true
+    }
+}
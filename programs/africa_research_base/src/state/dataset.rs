@@ -3,6 +3,45 @@ use anchor_lang::prelude::*;
 #[account]
 #[derive(InitSpace)]
 pub struct Dataset {
+    pub account_version: u8, // Layout version; `version` below is the content revision
+    pub id: Pubkey,
+    pub contributor: Pubkey,
+    pub content_hash: [u8; 32],
+
+    #[max_len(1500)]
+    pub ai_metadata: Vec<u8>,
+
+    #[max_len(100)]
+    pub file_name: Vec<u8>,
+    pub dataset_index: u32,
+    pub file_size: u64,
+    pub data_uri: [u8; 256],
+    pub column_count: u64,
+    pub row_count: u64,
+    pub quality_score: u8,
+    pub upload_points: u32, // Tier reward currently credited to the contributor for this dataset
+    pub upload_timestamp: i64,
+    pub last_updated: Option<i64>,
+    pub version: u32,
+    pub download_count: u32,
+    pub is_active: bool,
+    pub moderated: bool, // Deactivated by a moderator; only a moderator may reactivate
+
+    // Running totals of review scores weighted by each reviewer's reputation at review time
+    pub review_count: u32,
+    pub review_score_total: u64,
+    pub review_weight_total: u64,
+    pub bump: u8
+}
+
+impl Dataset {
+    pub const ACCOUNT_VERSION: u8 = 1;
+}
+
+// Layout of datasets created before upload points, revisions, moderation and reviews,
+// kept only for `migrate_dataset`
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyDataset {
     pub id: Pubkey,
     pub contributor: Pubkey,
     pub content_hash: [u8; 32],
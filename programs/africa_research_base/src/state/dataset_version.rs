@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+// Snapshot of a dataset as it was before `update_dataset` replaced it
+#[account]
+#[derive(InitSpace)]
+pub struct DatasetVersion {
+    pub dataset: Pubkey,
+    pub version: u32,
+    pub content_hash: [u8; 32],
+
+    #[max_len(1500)]
+    pub ai_metadata: Vec<u8>,
+    pub data_uri: [u8; 256],
+    pub column_count: u64,
+    pub row_count: u64,
+    pub file_size: u64,
+    pub published_at: i64,
+    pub superseded_at: i64,
+    pub bump: u8
+}
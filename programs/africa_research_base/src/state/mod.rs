@@ -4,11 +4,35 @@ pub use registry::*;
 pub mod dataset;
 pub use dataset::*;
 
+pub mod dataset_version;
+pub use dataset_version::*;
+
+pub mod content_hash_index;
+pub use content_hash_index::*;
+
+pub mod attestation_nonce;
+pub use attestation_nonce::*;
+
 pub mod attribution;
 pub use attribution::*;
 
 pub mod citation;
 pub use citation::*;
 
+pub mod review;
+pub use review::*;
+
+pub mod reviewer_stake;
+pub use reviewer_stake::*;
+
+pub mod vesting_schedule;
+pub use vesting_schedule::*;
+
 pub mod reputation;
 pub use reputation::*;
+
+pub mod authority;
+pub use authority::*;
+
+pub mod config;
+pub use config::*;
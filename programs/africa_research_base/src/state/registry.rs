@@ -3,8 +3,23 @@ use anchor_lang::prelude::*;
 #[account]
 #[derive(InitSpace)]
 pub struct Registry {
+    pub account_version: u8,
     pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
     pub total_datasets: u64,
     pub total_downloads: u64,
     pub bump: u8
-}
\ No newline at end of file
+}
+
+impl Registry {
+    pub const ACCOUNT_VERSION: u8 = 1;
+}
+
+// Layout of the registry accounts seeded by `[b"registry", admin]`, kept only for `migrate_registry`
+#[derive(AnchorDeserialize)]
+pub struct LegacyRegistry {
+    pub admin: Pubkey,
+    pub total_datasets: u64,
+    pub total_downloads: u64,
+    pub bump: u8
+}
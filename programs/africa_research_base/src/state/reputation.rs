@@ -1,8 +1,18 @@
 use anchor_lang::prelude::*;
 
+// Derived from the standing score against the thresholds in config; ordered lowest first
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, InitSpace)]
+pub enum ContributorTier {
+    Novice,
+    Contributor,
+    Trusted,     // May review, and upload over `trusted_upload_size`
+    Curator,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Reputation {
+    pub account_version: u8,
     pub contributor: Pubkey,
     pub total_uploads: u32,
     pub dataset_count: u32, // Track number of datasets created by this contributor
@@ -10,7 +20,9 @@ pub struct Reputation {
     pub total_quality_score: u64,
     pub total_downloads: u64,
     pub total_citations: u32,
-    pub reputation_score: u32,
+    pub reputation_score: u64, // Standing; decays with inactivity, never reduced by redemption
+    pub earned_points: u64,    // Undecayed total the standing and redeemable balance derive from
+    pub tier: ContributorTier, // Recomputed with the score
 
     // New fields for advanced scoring
     pub total_reviews: u64,
@@ -21,9 +33,52 @@ pub struct Reputation {
     pub total_upload_points: u64,
     pub total_review_points: u64,
     pub total_activity_points: u64,
+    pub total_download_points: u64, // Priced at the download weight in force when credited
+    pub total_citation_points: u64, // Priced at the citation weight in force when credited
+    pub legacy_upload_points: u64,  // Migrated upload points not yet assigned to a migrated dataset
 
     // Token Redemption
-    pub claimed_points: u64,
+    pub claimed_points: u64,    // Lifetime points redeemed
+    pub redeemable_points: u64, // Spendable balance, credited as the score grows
+    pub vesting_count: u32,     // Vesting schedules opened; seeds the next one
+
+    pub bump: u8,
+}
+
+impl Reputation {
+    pub const ACCOUNT_VERSION: u8 = 1;
+}
 
+// Layout before review, activity and point tracking were added, kept only for `migrate_reputation`
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct EarlyReputation {
+    pub contributor: Pubkey,
+    pub total_uploads: u32,
+    pub download_time: i64,
+    pub total_quality_score: u64,
+    pub total_downloads: u64,
+    pub total_citations: u32,
+    pub reputation_score: u32,
+    pub bump: u8,
+}
+
+// Layout before `reputation_score` was widened to u64, kept only for `migrate_reputation`
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyReputation {
+    pub contributor: Pubkey,
+    pub total_uploads: u32,
+    pub dataset_count: u32,
+    pub download_time: i64,
+    pub total_quality_score: u64,
+    pub total_downloads: u64,
+    pub total_citations: u32,
+    pub reputation_score: u32,
+    pub total_reviews: u64,
+    pub last_activity_timestamp: i64,
+    pub daily_activity_points: u32,
+    pub total_upload_points: u64,
+    pub total_review_points: u64,
+    pub total_activity_points: u64,
+    pub claimed_points: u64,
     pub bump: u8,
 }
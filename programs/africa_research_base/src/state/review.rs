@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum ReviewVerdict {
+    Approve,
+    RequestChanges,
+    Reject,
+}
+
+// Each criterion is scored 0-100
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct ReviewRubric {
+    pub completeness: u8,
+    pub accuracy: u8,
+    pub documentation: u8,
+    pub relevance: u8,
+}
+
+impl ReviewRubric {
+    pub fn is_valid(&self) -> bool {
+        self.completeness <= 100
+            && self.accuracy <= 100
+            && self.documentation <= 100
+            && self.relevance <= 100
+    }
+
+    pub fn score(&self) -> u8 {
+        let total = self.completeness as u16
+            + self.accuracy as u16
+            + self.documentation as u16
+            + self.relevance as u16;
+        (total / 4) as u8
+    }
+}
+
+// One review per (dataset, reviewer); the comment itself lives off-chain under `comment_hash`
+#[account]
+#[derive(InitSpace)]
+pub struct Review {
+    pub dataset: Pubkey,
+    pub dataset_version: u32, // Version of the dataset that was reviewed
+    pub reviewer: Pubkey,
+    pub contributor: Pubkey,
+    pub verdict: ReviewVerdict,
+    pub rubric: ReviewRubric,
+    pub comment_hash: [u8; 32],
+    pub weight: u64, // Reviewer's reputation score + 1 when the review was submitted
+    pub created_at: i64,
+    pub credited: bool, // Review reward already paid to the reviewer
+    pub reward_points: u32, // Points credited for this review, clawed back if it is slashed
+    pub slashed: bool,  // Judged fraudulent by a moderator; no longer counts towards quality
+    pub bump: u8
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+// Tokens from one vested redemption, held in the beneficiary's escrow until they vest;
+// `[b"vesting", beneficiary, index]`. Each redemption gets its own schedule, so a new
+// redemption never pushes back tokens that were already vesting.
+#[account]
+#[derive(InitSpace)]
+pub struct VestingSchedule {
+    pub beneficiary: Pubkey,
+    pub index: u32,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+    pub bump: u8
+}
+
+impl VestingSchedule {
+    // Nothing before the cliff, then linear from `start_time` to `end_time`
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        if now < self.cliff_time {
+            0
+        } else if now >= self.end_time {
+            self.total_amount
+        } else {
+            let elapsed = (now - self.start_time) as u128;
+            let duration = (self.end_time - self.start_time) as u128;
+            (self.total_amount as u128 * elapsed / duration) as u64
+        }
+    }
+
+    pub fn claimable_amount(&self, now: i64) -> u64 {
+        self.vested_amount(now).saturating_sub(self.claimed_amount)
+    }
+
+    pub fn locked_amount(&self, now: i64) -> u64 {
+        self.total_amount.saturating_sub(self.vested_amount(now))
+    }
+}
@@ -0,0 +1,1123 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { PublicKey, Keypair, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { sha256 } from 'js-sha256';
+import { AfricaResearchBase } from '../target/types/africa_research_base';
+import { getOrCreateAssociatedTokenAccount, mintTo, TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import {
+  createDataUri,
+  deriveContentHashIndexPDA,
+  ensureArbMint,
+  ensureRegistryAndConfig,
+  generateContentHash,
+  signQualityAttestation,
+} from './utils';
+
+describe("Dataset lifecycle", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.AfricaResearchBase as Program<AfricaResearchBase>;
+
+  const admin = provider.wallet.publicKey;
+  const contributor = Keypair.generate();
+  const stranger = Keypair.generate();
+  const qualityOracle = Keypair.generate();
+
+  let registryPda: PublicKey;
+  let configPda: PublicKey;
+  let reputationPda: PublicKey;
+  let datasetPda: PublicKey;
+
+  const hashIndexPda = (contentHash: number[]): PublicKey => {
+    return deriveContentHashIndexPDA(contentHash, program.programId)[0];
+  };
+
+  const datasetVersionPda = (version: number): PublicKey => {
+    const versionBytes = Buffer.alloc(4);
+    versionBytes.writeUInt32LE(version);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("dataset_version"), datasetPda.toBuffer(), versionBytes],
+      program.programId
+    )[0];
+  };
+
+  let attestationNonce = 0;
+  const attest = (contributor: PublicKey, contentHash: number[], qualityScore: number, rowCount: anchor.BN, columnCount: anchor.BN, fileSize: anchor.BN) => {
+    return signQualityAttestation(
+      qualityOracle, registryPda, program.programId,
+      contributor, contentHash, qualityScore, rowCount, columnCount, fileSize, new anchor.BN(attestationNonce++)
+    );
+  };
+
+  // Replaces the dataset's contents; an unattested update omits the oracle's Ed25519 instruction
+  const updateDataset = async (
+    signer: Keypair,
+    contentHash: number[],
+    qualityScore: number,
+    rowCount: number,
+    columnCount: number,
+    fileSize: number,
+    attested = true
+  ) => {
+    const attestation = attest(signer.publicKey, contentHash, qualityScore, new anchor.BN(rowCount), new anchor.BN(columnCount), new anchor.BN(fileSize));
+    const dataset = await program.account.dataset.fetch(datasetPda);
+    await program.methods
+      .updateDataset(
+        contentHash,
+        Buffer.from(JSON.stringify({ version: dataset.version + 1 }), 'utf-8'),
+        createDataUri("https://drive.google.com/file/d/lifecycle-v" + (dataset.version + 1) + "/view"),
+        new anchor.BN(columnCount),
+        new anchor.BN(rowCount),
+        new anchor.BN(fileSize),
+        qualityScore,
+        attestation.nonce
+      )
+      .accounts({
+        contributor: signer.publicKey,
+        registry: registryPda,
+        config: configPda,
+        dataset: datasetPda,
+        datasetVersion: datasetVersionPda(dataset.version),
+        hashIndex: hashIndexPda(contentHash),
+        reputation: PublicKey.findProgramAddressSync(
+          [Buffer.from("reputation"), signer.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        systemProgram: SystemProgram.programId,
+        ...attestation.accounts,
+      })
+      .preInstructions(attested ? [attestation.instruction] : [])
+      .signers([signer])
+      .rpc();
+  };
+
+  before(async () => {
+    for (const keypair of [contributor, stranger]) {
+      const signature = await provider.connection.requestAirdrop(
+        keypair.publicKey,
+        2 * anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(signature);
+    }
+
+    ({ registry: registryPda, config: configPda } = await ensureRegistryAndConfig(program, provider));
+    [reputationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation"), contributor.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .grantRole({ qualityOracle: {} })
+      .accounts({
+        admin: admin,
+        registry: registryPda,
+        adminAuthority: null,
+        holder: qualityOracle.publicKey,
+        authority: PublicKey.findProgramAddressSync(
+          [Buffer.from("authority"), registryPda.toBuffer(), qualityOracle.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .initializeReputation()
+      .accounts({
+        user: contributor.publicKey,
+        contributor: contributor.publicKey,
+        reputation: reputationPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([contributor])
+      .rpc();
+
+    const datasetIndex = Buffer.alloc(4);
+    datasetIndex.writeUInt32LE(0);
+    [datasetPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dataset"), contributor.publicKey.toBuffer(), datasetIndex],
+      program.programId
+    );
+
+    const contentHash = generateContentHash("lifecycle_v1_" + Date.now());
+    const attestation = attest(contributor.publicKey, contentHash, 75, new anchor.BN(100), new anchor.BN(5), new anchor.BN(2048));
+    await program.methods
+      .createDataset(
+        contentHash,
+        Buffer.from('{"version": 1}', 'utf-8'),
+        Buffer.from("lifecycle.csv", 'utf-8'),
+        new anchor.BN(2048),
+        createDataUri("https://drive.google.com/file/d/lifecycle-v1/view"),
+        new anchor.BN(5),
+        new anchor.BN(100),
+        75,
+        attestation.nonce
+      )
+      .accounts({
+        user: contributor.publicKey,
+        contributor: contributor.publicKey,
+        registry: registryPda,
+        config: configPda,
+        dataset: datasetPda,
+        hashIndex: hashIndexPda(contentHash),
+        reputation: reputationPda,
+        systemProgram: SystemProgram.programId,
+        ...attestation.accounts,
+      })
+      .preInstructions([attestation.instruction])
+      .signers([contributor])
+      .rpc();
+  });
+
+  describe("Dataset Updates", () => {
+    it("Should archive the previous version and bump the version counter", async () => {
+      const before = await program.account.dataset.fetch(datasetPda);
+      expect(before.version).to.equal(1);
+
+      const newContentHash = generateContentHash("lifecycle_v2_" + Date.now());
+      await updateDataset(contributor, newContentHash, 80, 250, 6, 4096);
+
+      const after = await program.account.dataset.fetch(datasetPda);
+      expect(after.version).to.equal(2);
+      expect(after.contentHash).to.deep.equal(newContentHash);
+      expect(after.rowCount.toNumber()).to.equal(250);
+      expect(after.qualityScore).to.equal(80);
+      expect(after.uploadPoints).to.equal(300);
+      expect(after.lastUpdated).to.not.be.null;
+
+      const archived = await program.account.datasetVersion.fetch(datasetVersionPda(1));
+      expect(archived.version).to.equal(1);
+      expect(archived.contentHash).to.deep.equal(before.contentHash);
+      expect(archived.rowCount.toNumber()).to.equal(100);
+      expect(archived.fileSize.toNumber()).to.equal(2048);
+      expect(after.fileSize.toNumber()).to.equal(4096);
+
+      const reputation = await program.account.reputation.fetch(reputationPda);
+      expect(reputation.totalQualityScore.toNumber()).to.equal(80);
+    });
+
+    it("Should reject new contents without a fresh quality attestation", async () => {
+      try {
+        await updateDataset(contributor, generateContentHash("lifecycle_unattested_" + Date.now()), 95, 250, 6, 4096, false);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidAttestation");
+      }
+    });
+
+    it("Should reject updates from anyone but the contributor", async () => {
+      try {
+        await updateDataset(stranger, generateContentHash("lifecycle_hijack_" + Date.now()), 95, 1, 1, 4096);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedUpdate");
+      }
+    });
+
+    it("Should require the Trusted tier to grow a revision over the trusted size", async () => {
+      const { trustedUploadSize } = (await program.account.programConfig.fetch(configPda)).scoring;
+      try {
+        await updateDataset(
+          contributor, generateContentHash("lifecycle_oversized_" + Date.now()), 80, 250, 6,
+          trustedUploadSize.toNumber() + 1
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InsufficientTier");
+      }
+    });
+  });
+
+  describe("Dataset Activation", () => {
+    const setStatus = async (method: "deactivateDataset" | "reactivateDataset", signer: Keypair) => {
+      await program.methods[method]()
+        .accounts({
+          signer: signer.publicKey,
+          registry: registryPda,
+          signerAuthority: null,
+          dataset: datasetPda,
+        })
+        .signers([signer])
+        .rpc();
+    };
+
+    it("Should reject deactivation by a wallet that is neither contributor nor moderator", async () => {
+      try {
+        await setStatus("deactivateDataset", stranger);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedUpdate");
+      }
+    });
+
+    it("Should reject downloads against an inactive dataset", async () => {
+      await setStatus("deactivateDataset", contributor);
+
+      const [adminAuthority] = PublicKey.findProgramAddressSync(
+        [Buffer.from("authority"), registryPda.toBuffer(), admin.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .grantRole({ reputationOracle: {} })
+        .accounts({
+          admin: admin,
+          registry: registryPda,
+          adminAuthority: null,
+          holder: admin,
+          authority: adminAuthority,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .recordDownload()
+          .accounts({
+            downloader: stranger.publicKey,
+            registry: registryPda,
+            config: configPda,
+            dataset: datasetPda,
+            attribution: PublicKey.findProgramAddressSync(
+              [Buffer.from("attribution"), datasetPda.toBuffer(), stranger.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            reputation: reputationPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([stranger])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("DatasetInactive");
+      }
+    });
+
+    it("Should reject updates to an inactive dataset", async () => {
+      try {
+        await updateDataset(contributor, generateContentHash("lifecycle_inactive_" + Date.now()), 80, 250, 6, 4096);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("DatasetInactive");
+      }
+    });
+
+    it("Should let the contributor reactivate their own dataset", async () => {
+      await setStatus("reactivateDataset", contributor);
+
+      const datasetAccount = await program.account.dataset.fetch(datasetPda);
+      expect(datasetAccount.isActive).to.equal(true);
+    });
+  });
+
+  describe("Duplicate Detection", () => {
+    it("Should map the content hash back to the dataset", async () => {
+      const datasetAccount = await program.account.dataset.fetch(datasetPda);
+      const index = await program.account.contentHashIndex.fetch(hashIndexPda(datasetAccount.contentHash));
+      expect(index.dataset.toString()).to.equal(datasetPda.toString());
+      expect(index.contributor.toString()).to.equal(contributor.publicKey.toString());
+    });
+
+    it("Should reject the same bytes registered by another wallet", async () => {
+      const datasetAccount = await program.account.dataset.fetch(datasetPda);
+      const [strangerReputation] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation"), stranger.publicKey.toBuffer()],
+        program.programId
+      );
+      const datasetIndex = Buffer.alloc(4);
+      datasetIndex.writeUInt32LE(0);
+      const [strangerDataset] = PublicKey.findProgramAddressSync(
+        [Buffer.from("dataset"), stranger.publicKey.toBuffer(), datasetIndex],
+        program.programId
+      );
+
+      await program.methods
+        .initializeReputation()
+        .accounts({
+          user: stranger.publicKey,
+          contributor: stranger.publicKey,
+          reputation: strangerReputation,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+
+      try {
+        const attestation = attest(stranger.publicKey, datasetAccount.contentHash, 90, new anchor.BN(250), new anchor.BN(6), new anchor.BN(2048));
+        await program.methods
+          .createDataset(
+            datasetAccount.contentHash,
+            Buffer.from('{"copy": true}', 'utf-8'),
+            Buffer.from("copy.csv", 'utf-8'),
+            new anchor.BN(2048),
+            createDataUri("https://drive.google.com/file/d/copy/view"),
+            new anchor.BN(6),
+            new anchor.BN(250),
+            90,
+            attestation.nonce
+          )
+          .accounts({
+            user: stranger.publicKey,
+            contributor: stranger.publicKey,
+            registry: registryPda,
+            config: configPda,
+            dataset: strangerDataset,
+            hashIndex: hashIndexPda(datasetAccount.contentHash),
+            reputation: strangerReputation,
+            systemProgram: SystemProgram.programId,
+            ...attestation.accounts,
+          })
+          .preInstructions([attestation.instruction])
+          .signers([stranger])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("DuplicateDataset");
+      }
+    });
+  });
+
+  describe("Download Attribution", () => {
+    const recordDownload = async (downloader: Keypair) => {
+      const [attributionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("attribution"), datasetPda.toBuffer(), downloader.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .recordDownload()
+        .accounts({
+          downloader: downloader.publicKey,
+          registry: registryPda,
+          config: configPda,
+          dataset: datasetPda,
+          attribution: attributionPda,
+          reputation: reputationPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([downloader])
+        .rpc();
+      return attributionPda;
+    };
+
+    it("Should record a download and credit the contributor", async () => {
+      const registryBefore = await program.account.registry.fetch(registryPda);
+      const datasetBefore = await program.account.dataset.fetch(datasetPda);
+      const reputationBefore = await program.account.reputation.fetch(reputationPda);
+
+      const attributionPda = await recordDownload(stranger);
+
+      const attribution = await program.account.attribution.fetch(attributionPda);
+      expect(attribution.datasetId.toString()).to.equal(datasetPda.toString());
+      expect(attribution.downloader.toString()).to.equal(stranger.publicKey.toString());
+      expect(attribution.contributor.toString()).to.equal(contributor.publicKey.toString());
+
+      const datasetAfter = await program.account.dataset.fetch(datasetPda);
+      expect(datasetAfter.downloadCount).to.equal(datasetBefore.downloadCount + 1);
+
+      const registryAfter = await program.account.registry.fetch(registryPda);
+      expect(registryAfter.totalDownloads.toNumber()).to.equal(registryBefore.totalDownloads.toNumber() + 1);
+
+      const reputationAfter = await program.account.reputation.fetch(reputationPda);
+      expect(reputationAfter.totalDownloads.toNumber()).to.equal(reputationBefore.totalDownloads.toNumber() + 1);
+      expect(reputationAfter.reputationScore.toNumber())
+        .to.be.greaterThan(reputationBefore.reputationScore.toNumber());
+    });
+
+    it("Should reject a repeat download from the same wallet", async () => {
+      try {
+        await recordDownload(stranger);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("already in use");
+      }
+    });
+
+    it("Should reject the contributor downloading their own dataset", async () => {
+      try {
+        await recordDownload(contributor);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("SelfDownload");
+      }
+    });
+  });
+
+  describe("Citations", () => {
+    // Matches PublicationReference::publication_hash: kind index + normalized identifier
+    const publicationHash = (kindIndex: number, identifier: string): number[] => {
+      return Array.from(Buffer.from(sha256(Buffer.concat([Buffer.from([kindIndex]), Buffer.from(identifier, 'utf-8')])), 'hex'));
+    };
+
+    const doiReference = (doi: string) => ({
+      kind: { doi: {} },
+      identifier: doi,
+      titleHash: generateContentHash("Rainfall variability across the Sahel"),
+      year: 2024,
+      venue: "Journal of African Climate Data",
+    });
+
+    const recordCitation = async (citer: Keypair, reference: any, hash: number[]) => {
+      const [citationPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("citation"), datasetPda.toBuffer(), citer.publicKey.toBuffer(), Buffer.from(hash)],
+        program.programId
+      );
+      await program.methods
+        .recordCitation(hash, reference)
+        .accounts({
+          citer: citer.publicKey,
+          config: configPda,
+          dataset: datasetPda,
+          citation: citationPda,
+          reputation: reputationPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([citer])
+        .rpc();
+      return citationPda;
+    };
+
+    it("Should record a DOI citation and credit the contributor", async () => {
+      const reputationBefore = await program.account.reputation.fetch(reputationPda);
+      const reference = doiReference("10.1000/ARB.Lifecycle.2024");
+
+      const citationPda = await recordCitation(stranger, reference, publicationHash(0, "10.1000/arb.lifecycle.2024"));
+
+      const citation = await program.account.citation.fetch(citationPda);
+      expect(citation.citer.toString()).to.equal(stranger.publicKey.toString());
+      expect(citation.contributor.toString()).to.equal(contributor.publicKey.toString());
+      expect(citation.reference.identifier).to.equal(reference.identifier);
+      expect(citation.reference.year).to.equal(2024);
+      expect(citation.reference.kind).to.deep.equal({ doi: {} });
+
+      const reputationAfter = await program.account.reputation.fetch(reputationPda);
+      expect(reputationAfter.totalCitations).to.equal(reputationBefore.totalCitations + 1);
+    });
+
+    it("Should treat DOIs case-insensitively when deduplicating", async () => {
+      try {
+        await recordCitation(
+          stranger,
+          doiReference("10.1000/arb.lifecycle.2024"),
+          publicationHash(0, "10.1000/arb.lifecycle.2024")
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("already in use");
+      }
+    });
+
+    it("Should treat arXiv revisions as the same publication", async () => {
+      const arxivReference = (id: string) => ({ ...doiReference(""), kind: { arxiv: {} }, identifier: id });
+      await recordCitation(stranger, arxivReference("2401.01234v1"), publicationHash(1, "2401.01234"));
+
+      try {
+        await recordCitation(stranger, arxivReference("2401.01234v2"), publicationHash(1, "2401.01234"));
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("already in use");
+      }
+    });
+
+    it("Should treat spelling variants of a URL as the same publication", async () => {
+      const urlReference = (url: string) => ({ ...doiReference(""), kind: { url: {} }, identifier: url });
+      const canonical = publicationHash(3, "example.org/papers/sahel-rainfall");
+      await recordCitation(stranger, urlReference("https://www.Example.org/papers/sahel-rainfall/"), canonical);
+
+      for (const variant of [
+        "http://example.org/papers/sahel-rainfall",
+        "https://example.org/papers/sahel-rainfall#abstract",
+      ]) {
+        try {
+          await recordCitation(stranger, urlReference(variant), canonical);
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.toString()).to.include("already in use");
+        }
+      }
+    });
+
+    it("Should reject a malformed DOI", async () => {
+      try {
+        await recordCitation(stranger, doiReference("11.1000/not-a-doi"), publicationHash(0, "11.1000/not-a-doi"));
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidPublication");
+      }
+    });
+
+    it("Should treat the ISBN-10 and ISBN-13 of a book as the same publication", async () => {
+      const isbnReference = (isbn: string) => ({ ...doiReference(""), kind: { isbn: {} }, identifier: isbn });
+      const canonical = publicationHash(2, "9780306406157");
+      await recordCitation(stranger, isbnReference("0-306-40615-2"), canonical);
+
+      try {
+        await recordCitation(stranger, isbnReference("978-0-306-40615-7"), canonical);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("already in use");
+      }
+    });
+
+    it("Should reject an ISBN with a bad check digit", async () => {
+      const reference = { ...doiReference(""), kind: { isbn: {} }, identifier: "978-0-306-40615-8" };
+      try {
+        await recordCitation(stranger, reference, publicationHash(2, "9780306406158"));
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidPublication");
+      }
+    });
+
+    it("Should reject a publication hash that does not match the reference", async () => {
+      try {
+        await recordCitation(stranger, doiReference("10.1000/arb.other"), generateContentHash("some_other_paper"));
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidPublication");
+      }
+    });
+
+    it("Should reject the contributor citing their own dataset", async () => {
+      try {
+        await recordCitation(contributor, doiReference("10.1000/arb.self"), publicationHash(0, "10.1000/arb.self"));
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("SelfCitation");
+      }
+    });
+  });
+
+  describe("Reviews", () => {
+    // Averages to 40, below the first upload tier threshold
+    const rubric = { completeness: 50, accuracy: 40, documentation: 30, relevance: 40 };
+    const commentHash = generateContentHash("Clean schema, missing units on two columns");
+
+    const authorityPda = (holder: PublicKey): PublicKey => {
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("authority"), registryPda.toBuffer(), holder.toBuffer()],
+        program.programId
+      )[0];
+    };
+
+    const reviewStakeVault = PublicKey.findProgramAddressSync(
+      [Buffer.from("review_stake_vault")],
+      program.programId
+    )[0];
+    let arbMint: PublicKey;
+    let strangerTokenAccount: PublicKey;
+    let minReviewStake: anchor.BN;
+
+    const reviewerStakePda = (reviewer: PublicKey): PublicKey => {
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("review_stake"), reviewer.toBuffer()],
+        program.programId
+      )[0];
+    };
+
+    const reviewPda = (reviewer: PublicKey): PublicKey => {
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("review"), datasetPda.toBuffer(), reviewer.toBuffer()],
+        program.programId
+      )[0];
+    };
+
+    const submitReview = async (reviewer: Keypair, reviewRubric: any) => {
+      await program.methods
+        .submitReview({ approve: {} }, reviewRubric, commentHash)
+        .accounts({
+          reviewer: reviewer.publicKey,
+          registry: registryPda,
+          reviewerAuthority: authorityPda(reviewer.publicKey),
+          config: configPda,
+          dataset: datasetPda,
+          review: reviewPda(reviewer.publicKey),
+          reviewerReputation: PublicKey.findProgramAddressSync(
+            [Buffer.from("reputation"), reviewer.publicKey.toBuffer()],
+            program.programId
+          )[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([reviewer])
+        .rpc();
+    };
+
+    const payReviewReward = async (reviewer: Keypair) => {
+      const [reviewerReputation] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation"), reviewer.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .updateReputationReview()
+        .accounts({
+          oracle: admin,
+          registry: registryPda,
+          oracleAuthority: authorityPda(admin),
+          config: configPda,
+          contributor: reviewer.publicKey,
+          reputation: reviewerReputation,
+          reviewerAuthority: authorityPda(reviewer.publicKey),
+          review: reviewPda(reviewer.publicKey),
+          reviewerStake: reviewerStakePda(reviewer.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([reviewer])
+        .rpc();
+      return reviewerReputation;
+    };
+
+    const stakeReview = async (reviewer: Keypair, amount: anchor.BN) => {
+      await program.methods
+        .stakeReview(amount)
+        .accounts({
+          reviewer: reviewer.publicKey,
+          config: configPda,
+          reviewStakeVault: reviewStakeVault,
+          reviewerTokenAccount: strangerTokenAccount,
+          reviewerStake: reviewerStakePda(reviewer.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([reviewer])
+        .rpc();
+    };
+
+    before(async () => {
+      for (const holder of [stranger.publicKey, contributor.publicKey]) {
+        await program.methods
+          .grantRole({ reviewer: {} })
+          .accounts({
+            admin: admin,
+            registry: registryPda,
+            adminAuthority: null,
+            holder: holder,
+            authority: authorityPda(holder),
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+      }
+
+      arbMint = await ensureArbMint(program, provider);
+      minReviewStake = (await program.account.programConfig.fetch(configPda)).scoring.minReviewStake;
+      const payer = (provider.wallet as anchor.Wallet).payer;
+      strangerTokenAccount = (await getOrCreateAssociatedTokenAccount(
+        provider.connection, payer, arbMint, stranger.publicKey
+      )).address;
+      await mintTo(
+        provider.connection, payer, arbMint, strangerTokenAccount, payer,
+        minReviewStake.muln(2).toNumber()
+      );
+    });
+
+    const strangerReputation = PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation"), stranger.publicKey.toBuffer()],
+      program.programId
+    )[0];
+    let originalScoring: any;
+
+    const updateScoring = async (scoring: any) => {
+      await program.methods
+        .updateConfig(scoring)
+        .accounts({
+          admin: admin,
+          registry: registryPda,
+          adminAuthority: null,
+          config: configPda,
+        })
+        .rpc();
+    };
+
+    after(async () => {
+      if (originalScoring) {
+        await updateScoring(originalScoring);
+      }
+    });
+
+    it("Should reject reviews from contributors below the Trusted tier", async () => {
+      try {
+        await submitReview(stranger, rubric);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InsufficientTier");
+      }
+    });
+
+    it("Should promote a contributor once their standing passes the Trusted threshold", async () => {
+      originalScoring = (await program.account.programConfig.fetch(configPda)).scoring;
+      await updateScoring({
+        ...originalScoring,
+        contributorTierThreshold: new anchor.BN(1),
+        trustedTierThreshold: new anchor.BN(2),
+        curatorTierThreshold: new anchor.BN(1000000),
+      });
+
+      await program.methods
+        .updateReputationActivity()
+        .accounts({
+          oracle: admin,
+          registry: registryPda,
+          oracleAuthority: authorityPda(admin),
+          config: configPda,
+          contributor: stranger.publicKey,
+          reputation: strangerReputation,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+
+      const reputation = await program.account.reputation.fetch(strangerReputation);
+      expect(reputation.tier).to.deep.equal({ trusted: {} });
+    });
+
+    it("Should store a review bound to the dataset", async () => {
+      await submitReview(stranger, rubric);
+
+      const review = await program.account.review.fetch(reviewPda(stranger.publicKey));
+      expect(review.dataset.toString()).to.equal(datasetPda.toString());
+      expect(review.datasetVersion).to.equal((await program.account.dataset.fetch(datasetPda)).version);
+      expect(review.reviewer.toString()).to.equal(stranger.publicKey.toString());
+      expect(review.verdict).to.deep.equal({ approve: {} });
+      expect(review.rubric.accuracy).to.equal(40);
+      expect(review.commentHash).to.deep.equal(commentHash);
+      expect(review.credited).to.equal(false);
+    });
+
+    it("Should reject a second review from the same reviewer", async () => {
+      try {
+        await submitReview(stranger, rubric);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("already in use");
+      }
+    });
+
+    it("Should reject the contributor reviewing their own dataset", async () => {
+      try {
+        await submitReview(contributor, rubric);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("SelfReview");
+      }
+    });
+
+    it("Should not pay the review reward before the reviewer stakes", async () => {
+      // Staked, but below the configured minimum
+      await stakeReview(stranger, new anchor.BN(1));
+      try {
+        await payReviewReward(stranger);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InsufficientStake");
+      }
+    });
+
+    it("Should pay the review reward only once", async () => {
+      await stakeReview(stranger, minReviewStake);
+
+      const reviewerReputation = await payReviewReward(stranger);
+      const afterFirst = await program.account.reputation.fetch(reviewerReputation);
+      const creditedReview = await program.account.review.fetch(reviewPda(stranger.publicKey));
+      expect(creditedReview.credited).to.equal(true);
+      expect(creditedReview.rewardPoints).to.equal(
+        (await program.account.programConfig.fetch(configPda)).scoring.reviewReward
+      );
+
+      try {
+        await payReviewReward(stranger);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("ReviewAlreadyCredited");
+      }
+
+      const afterSecond = await program.account.reputation.fetch(reviewerReputation);
+      expect(afterSecond.totalReviewPoints.toNumber()).to.equal(afterFirst.totalReviewPoints.toNumber());
+    });
+
+    it("Should not rescore an inactive dataset", async () => {
+      const setStatus = async (method: "deactivateDataset" | "reactivateDataset") => {
+        await program.methods[method]()
+          .accounts({
+            signer: contributor.publicKey,
+            registry: registryPda,
+            signerAuthority: null,
+            dataset: datasetPda,
+          })
+          .signers([contributor])
+          .rpc();
+      };
+
+      await setStatus("deactivateDataset");
+      try {
+        await program.methods
+          .recalculateQualityScore()
+          .accounts({
+            config: configPda,
+            dataset: datasetPda,
+            reputation: reputationPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("DatasetInactive");
+      } finally {
+        await setStatus("reactivateDataset");
+      }
+    });
+
+    it("Should recompute the quality score from reviews and move the upload tier", async () => {
+      const datasetBefore = await program.account.dataset.fetch(datasetPda);
+      const reputationBefore = await program.account.reputation.fetch(reputationPda);
+      expect(datasetBefore.reviewCount).to.equal(1);
+      expect(datasetBefore.uploadPoints).to.equal(300);
+
+      await program.methods
+        .recalculateQualityScore()
+        .accounts({
+          config: configPda,
+          dataset: datasetPda,
+          reputation: reputationPda,
+        })
+        .rpc();
+
+      const datasetAfter = await program.account.dataset.fetch(datasetPda);
+      expect(datasetAfter.qualityScore).to.equal(40);
+      expect(datasetAfter.uploadPoints).to.equal(20);
+
+      const reputationAfter = await program.account.reputation.fetch(reputationPda);
+      expect(reputationAfter.totalUploadPoints.toNumber()).to.equal(reputationBefore.totalUploadPoints.toNumber() - 280);
+      expect(reputationAfter.totalQualityScore.toNumber()).to.equal(
+        reputationBefore.totalQualityScore.toNumber() - datasetBefore.qualityScore + 40
+      );
+    });
+
+    it("Should not carry reviews over to a new version", async () => {
+      await updateDataset(contributor, generateContentHash("lifecycle_reviewed_v3_" + Date.now()), 80, 250, 6, 4096);
+
+      const datasetAccount = await program.account.dataset.fetch(datasetPda);
+      expect(datasetAccount.reviewCount).to.equal(0);
+      expect(datasetAccount.reviewScoreTotal.toNumber()).to.equal(0);
+      expect(datasetAccount.reviewWeightTotal.toNumber()).to.equal(0);
+
+      try {
+        await program.methods
+          .recalculateQualityScore()
+          .accounts({
+            config: configPda,
+            dataset: datasetPda,
+            reputation: reputationPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("NoReviews");
+      }
+    });
+  });
+
+  describe("Dataset Closing", () => {
+    const closeDataset = async (superseded: { pubkey: PublicKey; isSigner: boolean; isWritable: boolean }[]) => {
+      const datasetAccount = await program.account.dataset.fetch(datasetPda);
+      await program.methods
+        .closeDataset()
+        .accounts({
+          contributor: contributor.publicKey,
+          registry: registryPda,
+          config: configPda,
+          dataset: datasetPda,
+          hashIndex: hashIndexPda(datasetAccount.contentHash),
+          reputation: reputationPda,
+        })
+        .remainingAccounts(superseded)
+        .signers([contributor])
+        .rpc();
+    };
+
+    // One (DatasetVersion, ContentHashIndex) pair per superseded version, oldest first
+    const supersededAccounts = async (version: number) => {
+      const accounts = [];
+      for (let archived = 1; archived < version; archived++) {
+        const versionAccount = await program.account.datasetVersion.fetch(datasetVersionPda(archived));
+        accounts.push(
+          { pubkey: datasetVersionPda(archived), isSigner: false, isWritable: true },
+          { pubkey: hashIndexPda(versionAccount.contentHash), isSigner: false, isWritable: true }
+        );
+      }
+      return accounts;
+    };
+
+    it("Should refuse to close a dataset without its superseded versions", async () => {
+      try {
+        await closeDataset([]);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("IncompleteVersionHistory");
+      }
+    });
+
+    it("Should close a dataset and keep the counters consistent", async () => {
+      const registryBefore = await program.account.registry.fetch(registryPda);
+      const reputationBefore = await program.account.reputation.fetch(reputationPda);
+      const datasetAccount = await program.account.dataset.fetch(datasetPda);
+      const firstVersion = await program.account.datasetVersion.fetch(datasetVersionPda(1));
+
+      await closeDataset(await supersededAccounts(datasetAccount.version));
+
+      expect(await provider.connection.getAccountInfo(datasetPda)).to.be.null;
+      expect(await provider.connection.getAccountInfo(hashIndexPda(datasetAccount.contentHash))).to.be.null;
+
+      // Superseded versions release their hashes and rent along with the dataset
+      expect(await provider.connection.getAccountInfo(datasetVersionPda(1))).to.be.null;
+      expect(await provider.connection.getAccountInfo(hashIndexPda(firstVersion.contentHash))).to.be.null;
+
+      const registryAfter = await program.account.registry.fetch(registryPda);
+      expect(registryAfter.totalDatasets.toNumber()).to.equal(registryBefore.totalDatasets.toNumber() - 1);
+
+      const reputationAfter = await program.account.reputation.fetch(reputationPda);
+      expect(reputationAfter.totalUploads).to.equal(reputationBefore.totalUploads - 1);
+      expect(reputationAfter.datasetCount).to.equal(reputationBefore.datasetCount);
+    });
+  });
+
+  describe("Review Staking", () => {
+    const reviewStakeVault = PublicKey.findProgramAddressSync(
+      [Buffer.from("review_stake_vault")],
+      program.programId
+    )[0];
+
+    const pdaOf = (seed: string, ...keys: PublicKey[]): PublicKey => {
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from(seed), ...keys.map((key) => key.toBuffer())],
+        program.programId
+      )[0];
+    };
+
+    const requestUnstake = async (amount: anchor.BN) => {
+      await program.methods
+        .requestUnstake(amount)
+        .accounts({
+          reviewer: stranger.publicKey,
+          config: configPda,
+          reviewerStake: pdaOf("review_stake", stranger.publicKey),
+        })
+        .signers([stranger])
+        .rpc();
+    };
+
+    it("Should keep stake backing a credited review locked", async () => {
+      const stake = await program.account.reviewerStake.fetch(pdaOf("review_stake", stranger.publicKey));
+      expect(stake.lockedAmount.toString()).to.equal(minReviewStake.toString());
+
+      try {
+        await requestUnstake(stake.amount);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("StakeLocked");
+      }
+    });
+
+    it("Should keep unbonding stake locked until the period ends", async () => {
+      const stakeBefore = await program.account.reviewerStake.fetch(pdaOf("review_stake", stranger.publicKey));
+      const unlocked = stakeBefore.amount.sub(stakeBefore.lockedAmount);
+      await requestUnstake(unlocked);
+
+      const stake = await program.account.reviewerStake.fetch(pdaOf("review_stake", stranger.publicKey));
+      expect(stake.amount.toString()).to.equal(stakeBefore.lockedAmount.toString());
+      expect(stake.unbondingAmount.toString()).to.equal(unlocked.toString());
+
+      const arbMint = (await program.account.programConfig.fetch(configPda)).arbMint;
+      const strangerTokenAccount = (await getOrCreateAssociatedTokenAccount(
+        provider.connection, (provider.wallet as anchor.Wallet).payer, arbMint, stranger.publicKey
+      )).address;
+      try {
+        await program.methods
+          .withdrawStake()
+          .accounts({
+            reviewer: stranger.publicKey,
+            config: configPda,
+            reviewStakeVault: reviewStakeVault,
+            reviewerTokenAccount: strangerTokenAccount,
+            reviewerStake: pdaOf("review_stake", stranger.publicKey),
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([stranger])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("StakeUnbonding");
+      }
+    });
+
+    it("Should let a moderator slash locked stake and void a review of a closed dataset", async () => {
+      await program.methods
+        .grantRole({ moderator: {} })
+        .accounts({
+          admin: admin,
+          registry: registryPda,
+          adminAuthority: null,
+          holder: admin,
+          authority: pdaOf("authority", registryPda, admin),
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const configAccount = await program.account.programConfig.fetch(configPda);
+      const stakeBefore = await program.account.reviewerStake.fetch(pdaOf("review_stake", stranger.publicKey));
+      const reputationBefore = await program.account.reputation.fetch(pdaOf("reputation", stranger.publicKey));
+      const reviewBefore = await program.account.review.fetch(pdaOf("review", datasetPda, stranger.publicKey));
+
+      // A reward change after crediting must not change what the slash claws back
+      const setScoring = async (scoring: any) => {
+        await program.methods
+          .updateConfig(scoring)
+          .accounts({
+            admin: admin,
+            registry: registryPda,
+            adminAuthority: null,
+            config: configPda,
+          })
+          .rpc();
+      };
+      await setScoring({ ...configAccount.scoring, reviewReward: configAccount.scoring.reviewReward + 25 });
+
+      try {
+        await program.methods
+          .slashReview()
+          .accounts({
+            moderator: admin,
+            registry: registryPda,
+            moderatorAuthority: pdaOf("authority", registryPda, admin),
+            config: configPda,
+            arbMint: configAccount.arbMint,
+            reviewStakeVault: reviewStakeVault,
+            dataset: datasetPda,
+            review: pdaOf("review", datasetPda, stranger.publicKey),
+            reviewerStake: pdaOf("review_stake", stranger.publicKey),
+            reviewerReputation: pdaOf("reputation", stranger.publicKey),
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+      } finally {
+        await setScoring(configAccount.scoring);
+      }
+
+      const review = await program.account.review.fetch(pdaOf("review", datasetPda, stranger.publicKey));
+      expect(review.slashed).to.equal(true);
+
+      const stakeAfter = await program.account.reviewerStake.fetch(pdaOf("review_stake", stranger.publicKey));
+      expect(stakeAfter.slashedTotal.toString()).to.equal(configAccount.scoring.minReviewStake.toString());
+      expect(stakeAfter.amount.toString()).to.equal(
+        stakeBefore.amount.sub(configAccount.scoring.minReviewStake).toString()
+      );
+      expect(stakeAfter.lockedAmount.toNumber()).to.equal(0);
+      expect(stakeAfter.unbondingAmount.toString()).to.equal(stakeBefore.unbondingAmount.toString());
+
+      const reputationAfter = await program.account.reputation.fetch(pdaOf("reputation", stranger.publicKey));
+      expect(reputationAfter.totalReviewPoints.toNumber()).to.equal(
+        reputationBefore.totalReviewPoints.toNumber() - reviewBefore.rewardPoints
+      );
+
+      // The reviewed dataset was closed above; slashing must not depend on it
+      expect(await provider.connection.getAccountInfo(datasetPda)).to.be.null;
+    });
+  });
+});
@@ -4,6 +4,14 @@ import { PublicKey, Keypair, SystemProgram } from '@solana/web3.js';
 import { expect } from 'chai';
 import { sha256 } from 'js-sha256';
 import { AfricaResearchBase } from '../target/types/africa_research_base';
+import {
+  createDataUri,
+  deriveContentHashIndexPDA,
+  deriveDatasetPDA,
+  deriveProgramDataAddress,
+  generateContentHash,
+  signQualityAttestation,
+} from './utils';
 
 describe("Africa Research Base (ARB)", () => {
   const provider = anchor.AnchorProvider.env();
@@ -12,11 +20,14 @@ describe("Africa Research Base (ARB)", () => {
   const program = anchor.workspace.AfricaResearchBase as Program<AfricaResearchBase>;
   
   let datasetRegistry: PublicKey;
+  let adminAuthority: PublicKey;
+  let configPda: PublicKey;
   const admin = provider.wallet.publicKey;
   
   const researcher1 = Keypair.generate();
   const researcher2 = Keypair.generate();
   const researcher3 = Keypair.generate();
+  const qualityOracle = Keypair.generate();
   
   const TEST_FILE_NAME = "climate_survey_uganda_2024.csv";
   const TEST_FILE_SIZE = 1024 * 1024; // 1MB
@@ -34,16 +45,27 @@ describe("Africa Research Base (ARB)", () => {
     suggestedTags: ["climate", "uganda", "survey", "environmental"]
   }));
 
-  const generateContentHash = (data: string): number[] => {
-    const hash = sha256(data);
-    return Array.from(Buffer.from(hash, 'hex')).slice(0, 32);
+  const hashIndexPda = (contentHash: number[]): PublicKey => {
+    return deriveContentHashIndexPDA(contentHash, program.programId)[0];
   };
 
-  const createDataUri = (uri: string): number[] => {
-    const buffer = Buffer.from(uri, 'utf-8');
-    const paddedBuffer = Buffer.alloc(256);
-    buffer.copy(paddedBuffer);
-    return Array.from(paddedBuffer);
+  // Datasets are seeded by the contributor's next dataset_index, as in create_dataset
+  const nextDatasetPda = async (researcher: Keypair): Promise<[PublicKey, PublicKey]> => {
+    const [repPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation"), researcher.publicKey.toBuffer()],
+      program.programId
+    );
+    const reputation = await program.account.reputation.fetch(repPda);
+    const [datasetPda] = await deriveDatasetPDA(researcher.publicKey, reputation.datasetCount, program.programId);
+    return [datasetPda, repPda];
+  };
+
+  let attestationNonce = 0;
+  const attest = (contributor: PublicKey, contentHash: number[], qualityScore: number, rowCount: anchor.BN, columnCount: anchor.BN, fileSize: anchor.BN) => {
+    return signQualityAttestation(
+      qualityOracle, datasetRegistry, program.programId,
+      contributor, contentHash, qualityScore, rowCount, columnCount, fileSize, new anchor.BN(attestationNonce++)
+    );
   };
 
   before(async () => {
@@ -60,14 +82,26 @@ describe("Africa Research Base (ARB)", () => {
 
     // Find registry PDA
     [datasetRegistry] = PublicKey.findProgramAddressSync(
-      [Buffer.from("registry"), admin.toBuffer()],
+      [Buffer.from("registry")],
+      program.programId
+    );
+
+    [adminAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("authority"), datasetRegistry.toBuffer(), admin.toBuffer()],
+      program.programId
+    );
+
+    [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
       program.programId
     );
   });
 
   describe("Registry Initialization", () => {
     it("Should initialize the dataset registry successfully", async () => {
-      try {
+      // Another test file may have created it already
+      const existed = (await program.account.registry.fetchNullable(datasetRegistry)) !== null;
+      if (!existed) {
         await program.methods
           .initializeRegistry()
           .accounts({
@@ -75,16 +109,38 @@ describe("Africa Research Base (ARB)", () => {
             user: admin,
             contributor: admin,
             registry: datasetRegistry,
+            program: program.programId,
+            programData: deriveProgramDataAddress(program.programId),
             systemProgram: SystemProgram.programId,
           })
           .rpc();
+      }
 
-        const registryAccount = await program.account.registry.fetch(datasetRegistry);
-        expect(registryAccount.admin.toString()).to.equal(admin.toString());
+      const registryAccount = await program.account.registry.fetch(datasetRegistry);
+      expect(registryAccount.admin.toString()).to.equal(admin.toString());
+      if (!existed) {
         expect(registryAccount.totalDatasets.toNumber()).to.equal(0);
+      }
+    });
+
+    it("Should reject registry initialization from a wallet other than the upgrade authority", async () => {
+      try {
+        await program.methods
+          .initializeRegistry()
+          .accounts({
+            admin: researcher1.publicKey,
+            user: researcher1.publicKey,
+            contributor: researcher1.publicKey,
+            registry: datasetRegistry,
+            program: program.programId,
+            programData: deriveProgramDataAddress(program.programId),
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([researcher1])
+          .rpc();
+        expect.fail("Should have thrown an error");
       } catch (error) {
-        // Registry might already be initialized
-        console.log("Registry initialization skipped - may already exist");
+        expect(error.toString()).to.include("UnauthorizedDeployer");
       }
     });
 
@@ -97,6 +153,8 @@ describe("Africa Research Base (ARB)", () => {
             user: admin,
             contributor: admin,
             registry: datasetRegistry,
+            program: program.programId,
+            programData: deriveProgramDataAddress(program.programId),
             systemProgram: SystemProgram.programId,
           })
           .rpc();
@@ -108,6 +166,167 @@ describe("Africa Research Base (ARB)", () => {
     });
   });
 
+  describe("Admin Transfer", () => {
+    it("Should reject accept_admin from a wallet that was not proposed", async () => {
+      await program.methods
+        .proposeAdmin(researcher1.publicKey)
+        .accounts({
+          admin: admin,
+          registry: datasetRegistry,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .acceptAdmin()
+          .accounts({
+            newAdmin: researcher2.publicKey,
+            registry: datasetRegistry,
+          })
+          .signers([researcher2])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        const errorMessage = error.toString();
+        expect(errorMessage).to.include("NotPendingAdmin");
+      }
+    });
+
+    it("Should transfer the admin in two steps and back again", async () => {
+      await program.methods
+        .acceptAdmin()
+        .accounts({
+          newAdmin: researcher1.publicKey,
+          registry: datasetRegistry,
+        })
+        .signers([researcher1])
+        .rpc();
+
+      let registryAccount = await program.account.registry.fetch(datasetRegistry);
+      expect(registryAccount.admin.toString()).to.equal(researcher1.publicKey.toString());
+      expect(registryAccount.pendingAdmin).to.be.null;
+
+      await program.methods
+        .proposeAdmin(admin)
+        .accounts({
+          admin: researcher1.publicKey,
+          registry: datasetRegistry,
+        })
+        .signers([researcher1])
+        .rpc();
+
+      await program.methods
+        .acceptAdmin()
+        .accounts({
+          newAdmin: admin,
+          registry: datasetRegistry,
+        })
+        .rpc();
+
+      registryAccount = await program.account.registry.fetch(datasetRegistry);
+      expect(registryAccount.admin.toString()).to.equal(admin.toString());
+    });
+
+    it("Should reject propose_admin from a non-admin", async () => {
+      try {
+        await program.methods
+          .proposeAdmin(researcher2.publicKey)
+          .accounts({
+            admin: researcher2.publicKey,
+            registry: datasetRegistry,
+          })
+          .signers([researcher2])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        const errorMessage = error.toString();
+        expect(errorMessage).to.include("UnauthorizedAdmin");
+      }
+    });
+  });
+
+  describe("Program Config", () => {
+    it("Should initialize the config with the default scoring values", async () => {
+      // Another test file may have created it already
+      if (!(await program.account.programConfig.fetchNullable(configPda))) {
+        await program.methods
+          .initializeConfig()
+          .accounts({
+            admin: admin,
+            registry: datasetRegistry,
+            config: configPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+      }
+
+      const configAccount = await program.account.programConfig.fetch(configPda);
+      expect(configAccount.scoring.uploadTier3Reward).to.equal(300);
+      expect(configAccount.scoring.reviewReward).to.equal(50);
+    });
+
+    it("Should reject a config with inverted upload thresholds", async () => {
+      const configAccount = await program.account.programConfig.fetch(configPda);
+      try {
+        await program.methods
+          .updateConfig({
+            ...configAccount.scoring,
+            uploadTier1Threshold: 80,
+            uploadTier2Threshold: 40,
+          })
+          .accounts({
+            admin: admin,
+            registry: datasetRegistry,
+            adminAuthority: null,
+            config: configPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        const errorMessage = error.toString();
+        expect(errorMessage).to.include("InvalidConfig");
+      }
+    });
+
+    it("Should reject a config that lets reviewers earn without staking", async () => {
+      const configAccount = await program.account.programConfig.fetch(configPda);
+      try {
+        await program.methods
+          .updateConfig({ ...configAccount.scoring, minReviewStake: new anchor.BN(0) })
+          .accounts({
+            admin: admin,
+            registry: datasetRegistry,
+            adminAuthority: null,
+            config: configPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidConfig");
+      }
+    });
+
+    it("Should reject update_config from a non-admin", async () => {
+      const configAccount = await program.account.programConfig.fetch(configPda);
+      try {
+        await program.methods
+          .updateConfig({ ...configAccount.scoring, reviewReward: 5000 })
+          .accounts({
+            admin: researcher1.publicKey,
+            registry: datasetRegistry,
+            adminAuthority: null,
+            config: configPda,
+          })
+          .signers([researcher1])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        const errorMessage = error.toString();
+        expect(errorMessage).to.include("MissingRole");
+      }
+    });
+  });
+
   describe("Dataset Creation", () => {
     before(async () => {
       // Initialize reputation accounts for all researchers
@@ -117,22 +336,32 @@ describe("Africa Research Base (ARB)", () => {
           program.programId
         );
         
-        try {
-          await program.methods
-            .initializeReputation()
-            .accounts({
-              admin: admin,
-              user: researcher.publicKey,
-              contributor: researcher.publicKey,
-              reputation: repPda,
-              systemProgram: SystemProgram.programId,
-            })
-            .signers([researcher])
-            .rpc();
-        } catch (error) {
-          console.log(`Reputation for ${researcher.publicKey.toString()} already initialized`);
-        }
+        await program.methods
+          .initializeReputation()
+          .accounts({
+            user: researcher.publicKey,
+            contributor: researcher.publicKey,
+            reputation: repPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([researcher])
+          .rpc();
       }
+
+      await program.methods
+        .grantRole({ qualityOracle: {} })
+        .accounts({
+          admin: admin,
+          registry: datasetRegistry,
+          adminAuthority: null,
+          holder: qualityOracle.publicKey,
+          authority: PublicKey.findProgramAddressSync(
+            [Buffer.from("authority"), datasetRegistry.toBuffer(), qualityOracle.publicKey.toBuffer()],
+            program.programId
+          )[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
     });
 
     describe("Successful Dataset Creation", () => {
@@ -141,17 +370,9 @@ describe("Africa Research Base (ARB)", () => {
         const dataUri = createDataUri("https://drive.google.com/file/d/1234567890/view");
         const fileNameBuffer = Buffer.from(TEST_FILE_NAME, 'utf-8');
         
-        // Create PDA matching Rust code: [b"dataset", contributor.key().as_ref()]
-        const [datasetPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("dataset"), researcher1.publicKey.toBuffer()],
-          program.programId
-        );
-        
-        const [repPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("reputation"), researcher1.publicKey.toBuffer()],
-          program.programId
-        );
+        const [datasetPda, repPda] = await nextDatasetPda(researcher1);
         
+        const attestation = attest(researcher1.publicKey, contentHash, TEST_QUALITY_SCORE, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(TEST_COLUMN_COUNT), new anchor.BN(TEST_FILE_SIZE));
         await program.methods
           .createDataset(
             contentHash,
@@ -161,17 +382,21 @@ describe("Africa Research Base (ARB)", () => {
             dataUri,
             new anchor.BN(TEST_COLUMN_COUNT),
             new anchor.BN(TEST_ROW_COUNT),
-            TEST_QUALITY_SCORE
+            TEST_QUALITY_SCORE,
+            attestation.nonce
           )
           .accounts({
-            admin: admin,
             user: researcher1.publicKey,
             contributor: researcher1.publicKey,
             dataset: datasetPda,
+            hashIndex: hashIndexPda(contentHash),
             registry: datasetRegistry,
+            config: configPda,
             reputation: repPda,
             systemProgram: SystemProgram.programId,
+            ...attestation.accounts,
           })
+          .preInstructions([attestation.instruction])
           .signers([researcher1])
           .rpc();
 
@@ -198,16 +423,9 @@ describe("Africa Research Base (ARB)", () => {
         const dataUri = createDataUri("https://drive.google.com/file/d/tiny/view");
         const fileNameBuffer = Buffer.from("tiny.csv", 'utf-8');
 
-        const [datasetPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("dataset"), researcher2.publicKey.toBuffer()],
-          program.programId
-        );
-
-        const [repPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("reputation"), researcher2.publicKey.toBuffer()],
-          program.programId
-        );
+        const [datasetPda, repPda] = await nextDatasetPda(researcher2);
 
+        const attestation = attest(researcher2.publicKey, contentHash, 50, new anchor.BN(1), new anchor.BN(1), new anchor.BN(1));
         await program.methods
           .createDataset(
             contentHash,
@@ -217,17 +435,21 @@ describe("Africa Research Base (ARB)", () => {
             dataUri,
             new anchor.BN(1),
             new anchor.BN(1),
-            50
+            50,
+            attestation.nonce
           )
           .accounts({
-            admin: admin,
             user: researcher2.publicKey,
             contributor: researcher2.publicKey,
             dataset: datasetPda,
+            hashIndex: hashIndexPda(contentHash),
             registry: datasetRegistry,
+            config: configPda,
             reputation: repPda,
             systemProgram: SystemProgram.programId,
+            ...attestation.accounts,
           })
+          .preInstructions([attestation.instruction])
           .signers([researcher2])
           .rpc();
 
@@ -235,44 +457,102 @@ describe("Africa Research Base (ARB)", () => {
         expect(datasetAccount.fileSize.toNumber()).to.equal(1);
       });
 
+      it("Should require the Trusted tier for uploads over the trusted size", async () => {
+        const contentHash = generateContentHash("large_novice_dataset_" + Date.now());
+        const dataUri = createDataUri("https://drive.google.com/file/d/large/view");
+        const fileNameBuffer = Buffer.from("large_dataset.csv", 'utf-8');
+        const maxSize = 99 * 1024 * 1024; // 99MB
+
+        const [datasetPda, repPda] = await nextDatasetPda(researcher3);
+
+        try {
+          const attestation = attest(researcher3.publicKey, contentHash, 100, new anchor.BN(10000), new anchor.BN(100), new anchor.BN(maxSize));
+          await program.methods
+            .createDataset(
+              contentHash,
+              TEST_AI_METADATA,
+              fileNameBuffer,
+              new anchor.BN(maxSize),
+              dataUri,
+              new anchor.BN(100),
+              new anchor.BN(10000),
+              100,
+              attestation.nonce
+            )
+            .accounts({
+              user: researcher3.publicKey,
+              contributor: researcher3.publicKey,
+              dataset: datasetPda,
+              hashIndex: hashIndexPda(contentHash),
+              registry: datasetRegistry,
+              config: configPda,
+              reputation: repPda,
+              systemProgram: SystemProgram.programId,
+              ...attestation.accounts,
+            })
+            .preInstructions([attestation.instruction])
+            .signers([researcher3])
+            .rpc();
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.toString()).to.include("InsufficientTier");
+        }
+      });
+
       it("Should create dataset with maximum allowed file size (99MB)", async () => {
         const contentHash = generateContentHash("large_dataset_" + Date.now());
         const dataUri = createDataUri("https://drive.google.com/file/d/large/view");
         const fileNameBuffer = Buffer.from("large_dataset.csv", 'utf-8');
         const maxSize = 99 * 1024 * 1024; // 99MB
 
-        const [datasetPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("dataset"), researcher3.publicKey.toBuffer()],
-          program.programId
-        );
+        const [datasetPda, repPda] = await nextDatasetPda(researcher3);
 
-        const [repPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("reputation"), researcher3.publicKey.toBuffer()],
-          program.programId
-        );
+        // Lift the tier gate so only the hard file size cap applies
+        const configAccount = await program.account.programConfig.fetch(configPda);
+        const setTrustedUploadSize = async (size: anchor.BN) => {
+          await program.methods
+            .updateConfig({ ...configAccount.scoring, trustedUploadSize: size })
+            .accounts({
+              admin: admin,
+              registry: datasetRegistry,
+              adminAuthority: null,
+              config: configPda,
+            })
+            .rpc();
+        };
+        await setTrustedUploadSize(new anchor.BN(100 * 1024 * 1024));
 
-        await program.methods
-          .createDataset(
-            contentHash,
-            TEST_AI_METADATA,
-            fileNameBuffer,
-            new anchor.BN(maxSize),
-            dataUri,
-            new anchor.BN(100),
-            new anchor.BN(10000),
-            100
-          )
-          .accounts({
-            admin: admin,
-            user: researcher3.publicKey,
-            contributor: researcher3.publicKey,
-            dataset: datasetPda,
-            registry: datasetRegistry,
-            reputation: repPda,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([researcher3])
-          .rpc();
+        try {
+          const attestation = attest(researcher3.publicKey, contentHash, 100, new anchor.BN(10000), new anchor.BN(100), new anchor.BN(maxSize));
+          await program.methods
+            .createDataset(
+              contentHash,
+              TEST_AI_METADATA,
+              fileNameBuffer,
+              new anchor.BN(maxSize),
+              dataUri,
+              new anchor.BN(100),
+              new anchor.BN(10000),
+              100,
+              attestation.nonce
+            )
+            .accounts({
+              user: researcher3.publicKey,
+              contributor: researcher3.publicKey,
+              dataset: datasetPda,
+              hashIndex: hashIndexPda(contentHash),
+              registry: datasetRegistry,
+              config: configPda,
+              reputation: repPda,
+              systemProgram: SystemProgram.programId,
+              ...attestation.accounts,
+            })
+            .preInstructions([attestation.instruction])
+            .signers([researcher3])
+            .rpc();
+        } finally {
+          await setTrustedUploadSize(configAccount.scoring.trustedUploadSize);
+        }
 
         const datasetAccount = await program.account.dataset.fetch(datasetPda);
         expect(datasetAccount.fileSize.toNumber()).to.equal(maxSize);
@@ -287,17 +567,10 @@ describe("Africa Research Base (ARB)", () => {
         const fileNameBuffer = Buffer.from("oversized.csv", 'utf-8');
         const oversizedFile = 101 * 1024 * 1024; // 101MB
 
-        const [datasetPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("dataset"), researcher1.publicKey.toBuffer()],
-          program.programId
-        );
-
-        const [repPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("reputation"), researcher1.publicKey.toBuffer()],
-          program.programId
-        );
+        const [datasetPda, repPda] = await nextDatasetPda(researcher1);
 
         try {
+          const attestation = attest(researcher1.publicKey, contentHash, TEST_QUALITY_SCORE, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(TEST_COLUMN_COUNT), new anchor.BN(oversizedFile));
           await program.methods
             .createDataset(
               contentHash,
@@ -307,17 +580,21 @@ describe("Africa Research Base (ARB)", () => {
               dataUri,
               new anchor.BN(TEST_COLUMN_COUNT),
               new anchor.BN(TEST_ROW_COUNT),
-              TEST_QUALITY_SCORE
+              TEST_QUALITY_SCORE,
+              attestation.nonce
             )
             .accounts({
-              admin: admin,
               user: researcher1.publicKey,
               contributor: researcher1.publicKey,
               dataset: datasetPda,
+              hashIndex: hashIndexPda(contentHash),
               registry: datasetRegistry,
+              config: configPda,
               reputation: repPda,
               systemProgram: SystemProgram.programId,
+              ...attestation.accounts,
             })
+            .preInstructions([attestation.instruction])
             .signers([researcher1])
             .rpc();
           expect.fail("Should have thrown an error");
@@ -336,17 +613,10 @@ describe("Africa Research Base (ARB)", () => {
         const dataUri = createDataUri("https://drive.google.com/file/d/invalid/view");
         const fileNameBuffer = Buffer.from("invalid_quality.csv", 'utf-8');
 
-        const [datasetPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("dataset"), researcher2.publicKey.toBuffer()],
-          program.programId
-        );
-
-        const [repPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("reputation"), researcher2.publicKey.toBuffer()],
-          program.programId
-        );
+        const [datasetPda, repPda] = await nextDatasetPda(researcher2);
 
         try {
+          const attestation = attest(researcher2.publicKey, contentHash, 150, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(TEST_COLUMN_COUNT), new anchor.BN(TEST_FILE_SIZE));
           await program.methods
             .createDataset(
               contentHash,
@@ -356,17 +626,21 @@ describe("Africa Research Base (ARB)", () => {
               dataUri,
               new anchor.BN(TEST_COLUMN_COUNT),
               new anchor.BN(TEST_ROW_COUNT),
-              150 // Invalid quality score
+              150, // Invalid quality score
+              attestation.nonce
             )
             .accounts({
-              admin: admin,
               user: researcher2.publicKey,
               contributor: researcher2.publicKey,
               dataset: datasetPda,
+              hashIndex: hashIndexPda(contentHash),
               registry: datasetRegistry,
+              config: configPda,
               reputation: repPda,
               systemProgram: SystemProgram.programId,
+              ...attestation.accounts,
             })
+            .preInstructions([attestation.instruction])
             .signers([researcher2])
             .rpc();
           expect.fail("Should have thrown an error");
@@ -384,17 +658,10 @@ describe("Africa Research Base (ARB)", () => {
         const dataUri = createDataUri("https://drive.google.com/file/d/zero/view");
         const fileNameBuffer = Buffer.from("zero.csv", 'utf-8');
 
-        const [datasetPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("dataset"), researcher3.publicKey.toBuffer()],
-          program.programId
-        );
-
-        const [repPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("reputation"), researcher3.publicKey.toBuffer()],
-          program.programId
-        );
+        const [datasetPda, repPda] = await nextDatasetPda(researcher3);
 
         try {
+          const attestation = attest(researcher3.publicKey, contentHash, TEST_QUALITY_SCORE, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(TEST_COLUMN_COUNT), new anchor.BN(0));
           await program.methods
             .createDataset(
               contentHash,
@@ -404,17 +671,21 @@ describe("Africa Research Base (ARB)", () => {
               dataUri,
               new anchor.BN(TEST_COLUMN_COUNT),
               new anchor.BN(TEST_ROW_COUNT),
-              TEST_QUALITY_SCORE
+              TEST_QUALITY_SCORE,
+              attestation.nonce
             )
             .accounts({
-              admin: admin,
               user: researcher3.publicKey,
               contributor: researcher3.publicKey,
               dataset: datasetPda,
+              hashIndex: hashIndexPda(contentHash),
               registry: datasetRegistry,
+              config: configPda,
               reputation: repPda,
               systemProgram: SystemProgram.programId,
+              ...attestation.accounts,
             })
+            .preInstructions([attestation.instruction])
             .signers([researcher3])
             .rpc();
           expect.fail("Should have thrown an error");
@@ -432,21 +703,10 @@ describe("Africa Research Base (ARB)", () => {
         const dataUri = createDataUri("https://drive.google.com/file/d/first/view");
         const fileNameBuffer = Buffer.from("first.csv", 'utf-8');
 
-        const [datasetPda] = PublicKey.findProgramAddressSync(
-          [
-            Buffer.from("dataset"),
-            researcher1.publicKey.toBuffer(),
-            Buffer.from(duplicateContentHash)
-          ],
-          program.programId
-        );
-
-        const [repPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("reputation"), researcher1.publicKey.toBuffer()],
-          program.programId
-        );
+        const [datasetPda, repPda] = await nextDatasetPda(researcher1);
 
         // First creation should succeed
+        const attestation = attest(researcher1.publicKey, duplicateContentHash, TEST_QUALITY_SCORE, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(TEST_COLUMN_COUNT), new anchor.BN(TEST_FILE_SIZE));
         await program.methods
           .createDataset(
             duplicateContentHash,
@@ -456,22 +716,28 @@ describe("Africa Research Base (ARB)", () => {
             dataUri,
             new anchor.BN(TEST_COLUMN_COUNT),
             new anchor.BN(TEST_ROW_COUNT),
-            TEST_QUALITY_SCORE
+            TEST_QUALITY_SCORE,
+            attestation.nonce
           )
           .accounts({
-            admin: admin,
             user: researcher1.publicKey,
             contributor: researcher1.publicKey,
             dataset: datasetPda,
+            hashIndex: hashIndexPda(duplicateContentHash),
             registry: datasetRegistry,
+            config: configPda,
             reputation: repPda,
             systemProgram: SystemProgram.programId,
+            ...attestation.accounts,
           })
+          .preInstructions([attestation.instruction])
           .signers([researcher1])
           .rpc();
 
-        // Second creation with same contributor should fail (PDA conflict)
+        // Second creation under the contributor's next dataset index is caught by the hash index
+        const [nextDataset] = await nextDatasetPda(researcher1);
         try {
+          const attestation = attest(researcher1.publicKey, duplicateContentHash, TEST_QUALITY_SCORE, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(TEST_COLUMN_COUNT), new anchor.BN(TEST_FILE_SIZE));
           await program.methods
             .createDataset(
               duplicateContentHash,
@@ -481,23 +747,26 @@ describe("Africa Research Base (ARB)", () => {
               createDataUri("https://drive.google.com/file/d/duplicate/view"),
               new anchor.BN(TEST_COLUMN_COUNT),
               new anchor.BN(TEST_ROW_COUNT),
-              TEST_QUALITY_SCORE
+              TEST_QUALITY_SCORE,
+              attestation.nonce
             )
             .accounts({
-              admin: admin,
               user: researcher1.publicKey,
               contributor: researcher1.publicKey,
-              dataset: datasetPda,
+              dataset: nextDataset,
+              hashIndex: hashIndexPda(duplicateContentHash),
               registry: datasetRegistry,
+              config: configPda,
               reputation: repPda,
               systemProgram: SystemProgram.programId,
+              ...attestation.accounts,
             })
+            .preInstructions([attestation.instruction])
             .signers([researcher1])
             .rpc();
           expect.fail("Should have thrown an error");
         } catch (error) {
-          const errorMessage = error.toString();
-          expect(errorMessage).to.include("already in use");
+          expect(error.toString()).to.include("DuplicateDataset");
         }
       });
 
@@ -506,17 +775,10 @@ describe("Africa Research Base (ARB)", () => {
         const dataUri = createDataUri("https://drive.google.com/file/d/columns/view");
         const fileNameBuffer = Buffer.from("many_columns.csv", 'utf-8');
 
-        const [datasetPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("dataset"), researcher1.publicKey.toBuffer()],
-          program.programId
-        );
-
-        const [repPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("reputation"), researcher1.publicKey.toBuffer()],
-          program.programId
-        );
+        const [datasetPda, repPda] = await nextDatasetPda(researcher1);
 
         try {
+          const attestation = attest(researcher1.publicKey, contentHash, TEST_QUALITY_SCORE, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(150), new anchor.BN(TEST_FILE_SIZE));
           await program.methods
             .createDataset(
               contentHash,
@@ -526,17 +788,21 @@ describe("Africa Research Base (ARB)", () => {
               dataUri,
               new anchor.BN(150), // Too many columns
               new anchor.BN(TEST_ROW_COUNT),
-              TEST_QUALITY_SCORE
+              TEST_QUALITY_SCORE,
+              attestation.nonce
             )
             .accounts({
-              admin: admin,
               user: researcher1.publicKey,
               contributor: researcher1.publicKey,
               dataset: datasetPda,
+              hashIndex: hashIndexPda(contentHash),
               registry: datasetRegistry,
+              config: configPda,
               reputation: repPda,
               systemProgram: SystemProgram.programId,
+              ...attestation.accounts,
             })
+            .preInstructions([attestation.instruction])
             .signers([researcher1])
             .rpc();
           expect.fail("Should have thrown an error");
@@ -549,132 +815,236 @@ describe("Africa Research Base (ARB)", () => {
         }
       });
     });
-  });
-
-  describe("Reputation System", () => {
-    describe("Upload Reputation Updates", () => {
-      it("Should update reputation after successful dataset upload", async () => {
-        const contentHash = generateContentHash("reputation_upload_test_" + Date.now());
-        const dataUri = createDataUri("https://drive.google.com/file/d/reputation/view");
-        const fileNameBuffer = Buffer.from("reputation_test.csv", 'utf-8');
-
-        const [datasetPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("dataset"), researcher1.publicKey.toBuffer()],
-          program.programId
-        );
-
-        const [repPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("reputation"), researcher1.publicKey.toBuffer()],
-          program.programId
-        );
 
+    describe("Quality Score Attestation", () => {
+      const createAttested = async (contentHash: number[], qualityScore: number, attestation: any) => {
+        const [datasetPda, repPda] = await nextDatasetPda(researcher1);
         await program.methods
           .createDataset(
             contentHash,
             TEST_AI_METADATA,
-            fileNameBuffer,
+            Buffer.from("attested.csv", 'utf-8'),
             new anchor.BN(TEST_FILE_SIZE),
-            dataUri,
+            createDataUri("https://drive.google.com/file/d/attested/view"),
             new anchor.BN(TEST_COLUMN_COUNT),
             new anchor.BN(TEST_ROW_COUNT),
-            90
+            qualityScore,
+            attestation.nonce
           )
           .accounts({
-            admin: admin,
             user: researcher1.publicKey,
             contributor: researcher1.publicKey,
             dataset: datasetPda,
+            hashIndex: hashIndexPda(contentHash),
             registry: datasetRegistry,
+            config: configPda,
             reputation: repPda,
             systemProgram: SystemProgram.programId,
+            ...attestation.accounts,
           })
+          .preInstructions([attestation.instruction])
           .signers([researcher1])
           .rpc();
+      };
 
-        const initialRep = await program.account.reputation.fetch(repPda);
-        const initialScore = initialRep.reputationScore;
+      it("Should reject a quality score the oracle did not sign", async () => {
+        const contentHash = generateContentHash("tampered_score_" + Date.now());
+        const attestation = attest(researcher1.publicKey, contentHash, 40, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(TEST_COLUMN_COUNT), new anchor.BN(TEST_FILE_SIZE));
+        try {
+          await createAttested(contentHash, 95, attestation);
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.toString()).to.include("InvalidAttestation");
+        }
+      });
+
+      it("Should reject a file size the oracle did not sign", async () => {
+        const contentHash = generateContentHash("tampered_size_" + Date.now());
+        const attestation = attest(researcher1.publicKey, contentHash, 95, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(TEST_COLUMN_COUNT), new anchor.BN(TEST_FILE_SIZE / 2));
+        try {
+          await createAttested(contentHash, 95, attestation);
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.toString()).to.include("InvalidAttestation");
+        }
+      });
+
+      it("Should reject an attestation issued to a different contributor", async () => {
+        const contentHash = generateContentHash("front_run_" + Date.now());
+        const attestation = attest(researcher2.publicKey, contentHash, 95, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(TEST_COLUMN_COUNT), new anchor.BN(TEST_FILE_SIZE));
+        try {
+          await createAttested(contentHash, 95, attestation);
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.toString()).to.include("InvalidAttestation");
+        }
+      });
 
+      it("Should reject an attestation signed by a wallet without the QualityOracle role", async () => {
+        const contentHash = generateContentHash("rogue_oracle_" + Date.now());
+        const attestation = signQualityAttestation(
+          researcher1, datasetRegistry, program.programId,
+          researcher1.publicKey, contentHash, 95, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(TEST_COLUMN_COUNT), new anchor.BN(TEST_FILE_SIZE), new anchor.BN(0)
+        );
+        try {
+          await createAttested(contentHash, 95, attestation);
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          // researcher1 was never granted a role, so no Authority account exists for them
+          expect(error.toString()).to.include("AccountNotInitialized");
+        }
+      });
+
+      it("Should reject a replayed attestation nonce", async () => {
+        const contentHash = generateContentHash("replayed_nonce_" + Date.now());
+        const attestation = attest(researcher1.publicKey, contentHash, 70, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(TEST_COLUMN_COUNT), new anchor.BN(TEST_FILE_SIZE));
+        await createAttested(contentHash, 70, attestation);
+
+        const nonceAccount = await program.account.attestationNonce.fetch(attestation.accounts.attestationNonce);
+        expect(nonceAccount.oracle.toString()).to.equal(qualityOracle.publicKey.toString());
+
+        const otherHash = generateContentHash("replayed_nonce_other_" + Date.now());
+        const replay = signQualityAttestation(
+          qualityOracle, datasetRegistry, program.programId,
+          researcher1.publicKey, otherHash, 70, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(TEST_COLUMN_COUNT), new anchor.BN(TEST_FILE_SIZE), attestation.nonce
+        );
+        try {
+          await createAttested(otherHash, 70, replay);
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.toString()).to.include("already in use");
+        }
+      });
+    });
+  });
+
+  describe("Reputation System", () => {
+    before(async () => {
+      await program.methods
+        .grantRole({ reputationOracle: {} })
+        .accounts({
+          admin: admin,
+          registry: datasetRegistry,
+          adminAuthority: null,
+          holder: admin,
+          authority: adminAuthority,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    });
+
+    describe("Upload Reputation Updates", () => {
+      const createRatedDataset = async (researcher: Keypair, label: string, qualityScore: number) => {
+        const contentHash = generateContentHash(label + "_" + Date.now());
+        const [datasetPda, repPda] = await nextDatasetPda(researcher);
+        const reputation = await program.account.reputation.fetch(repPda);
+
+        const attestation = attest(researcher.publicKey, contentHash, qualityScore, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(TEST_COLUMN_COUNT), new anchor.BN(TEST_FILE_SIZE));
         await program.methods
-          .updateReputationUpload(90)
+          .createDataset(
+            contentHash,
+            TEST_AI_METADATA,
+            Buffer.from(label + ".csv", 'utf-8'),
+            new anchor.BN(TEST_FILE_SIZE),
+            createDataUri("https://drive.google.com/file/d/" + label + "/view"),
+            new anchor.BN(TEST_COLUMN_COUNT),
+            new anchor.BN(TEST_ROW_COUNT),
+            qualityScore,
+            attestation.nonce
+          )
           .accounts({
-            admin: admin,
-            contributor: researcher1.publicKey,
+            user: researcher.publicKey,
+            contributor: researcher.publicKey,
+            dataset: datasetPda,
+            hashIndex: hashIndexPda(contentHash),
+            registry: datasetRegistry,
+            config: configPda,
             reputation: repPda,
             systemProgram: SystemProgram.programId,
+            ...attestation.accounts,
           })
-          .signers([researcher1])
+          .preInstructions([attestation.instruction])
+          .signers([researcher])
           .rpc();
 
+        return { repPda, datasetPda, before: reputation };
+      };
+
+      it("Should award tiered upload points when the dataset is created", async () => {
+        const { repPda, datasetPda, before } = await createRatedDataset(researcher1, "reputation_upload", 90);
+
         const updatedRep = await program.account.reputation.fetch(repPda);
-        expect(updatedRep.reputationScore).to.be.greaterThan(initialScore);
-        expect(updatedRep.totalUploads).to.be.greaterThan(0);
+        const datasetAccount = await program.account.dataset.fetch(datasetPda);
+        expect(datasetAccount.uploadPoints).to.equal(300);
+        expect(updatedRep.totalUploadPoints.toNumber()).to.equal(before.totalUploadPoints.toNumber() + 300);
+        expect(updatedRep.reputationScore.toNumber()).to.be.greaterThan(before.reputationScore.toNumber());
+        expect(updatedRep.totalUploads).to.equal(before.totalUploads + 1);
       });
 
       it("Should give higher reputation for better quality scores", async () => {
-        const [repPda1] = PublicKey.findProgramAddressSync(
-          [Buffer.from("reputation"), researcher2.publicKey.toBuffer()],
-          program.programId
-        );
+        const high = await createRatedDataset(researcher2, "high_quality", 95);
+        const low = await createRatedDataset(researcher3, "low_quality", 60);
 
-        const [repPda2] = PublicKey.findProgramAddressSync(
-          [Buffer.from("reputation"), researcher3.publicKey.toBuffer()],
-          program.programId
-        );
+        const highQualityRep = await program.account.reputation.fetch(high.repPda);
+        const lowQualityRep = await program.account.reputation.fetch(low.repPda);
 
-        const initialRep1 = await program.account.reputation.fetch(repPda1);
-        const initialScore1 = initialRep1.reputationScore;
+        const highQualityIncrease = highQualityRep.reputationScore.toNumber() - high.before.reputationScore.toNumber();
+        const lowQualityIncrease = lowQualityRep.reputationScore.toNumber() - low.before.reputationScore.toNumber();
 
-        const initialRep2 = await program.account.reputation.fetch(repPda2);
-        const initialScore2 = initialRep2.reputationScore;
+        expect(highQualityIncrease).to.be.greaterThan(lowQualityIncrease);
+      });
+
+      it("Should not credit an upload twice when re-scoring", async () => {
+        const { repPda, datasetPda } = await createRatedDataset(researcher1, "rescore", 80);
+        const before = await program.account.reputation.fetch(repPda);
 
         await program.methods
-          .updateReputationUpload(95)
+          .updateReputationUpload()
           .accounts({
-            admin: admin,
-            contributor: researcher2.publicKey,
-            reputation: repPda1,
-            systemProgram: SystemProgram.programId,
+            oracle: admin,
+            registry: datasetRegistry,
+            oracleAuthority: adminAuthority,
+            config: configPda,
+            contributor: researcher1.publicKey,
+            reputation: repPda,
+            dataset: datasetPda,
           })
-          .signers([researcher2])
           .rpc();
 
+        const after = await program.account.reputation.fetch(repPda);
+        expect(after.totalUploadPoints.toNumber()).to.equal(before.totalUploadPoints.toNumber());
+        expect(after.totalUploads).to.equal(before.totalUploads);
+      });
+    });
+
+    describe("Download Reputation Updates", () => {
+      const recordDownload = async (datasetPda: PublicKey, repPda: PublicKey, downloader: Keypair) => {
         await program.methods
-          .updateReputationUpload(60)
+          .recordDownload()
           .accounts({
-            admin: admin,
-            contributor: researcher3.publicKey,
-            reputation: repPda2,
+            downloader: downloader.publicKey,
+            registry: datasetRegistry,
+            config: configPda,
+            dataset: datasetPda,
+            attribution: PublicKey.findProgramAddressSync(
+              [Buffer.from("attribution"), datasetPda.toBuffer(), downloader.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            reputation: repPda,
             systemProgram: SystemProgram.programId,
           })
-          .signers([researcher3])
+          .signers([downloader])
           .rpc();
+      };
 
-        const highQualityRep = await program.account.reputation.fetch(repPda1);
-        const lowQualityRep = await program.account.reputation.fetch(repPda2);
-
-        const highQualityIncrease = highQualityRep.reputationScore - initialScore1;
-        const lowQualityIncrease = lowQualityRep.reputationScore - initialScore2;
-
-        expect(highQualityIncrease).to.be.greaterThan(lowQualityIncrease);
-      });
-    });
-
-    describe("Download Reputation Updates", () => {
       it("Should update reputation when dataset is downloaded", async () => {
         const contentHash = generateContentHash("download_test_" + Date.now());
         const dataUri = createDataUri("https://drive.google.com/file/d/download/view");
         const fileNameBuffer = Buffer.from("download_test.csv", 'utf-8');
 
-        const [datasetPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("dataset"), researcher1.publicKey.toBuffer()],
-          program.programId
-        );
-
-        const [repPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("reputation"), researcher1.publicKey.toBuffer()],
-          program.programId
-        );
+        const [datasetPda, repPda] = await nextDatasetPda(researcher1);
 
+        const attestation = attest(researcher1.publicKey, contentHash, TEST_QUALITY_SCORE, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(TEST_COLUMN_COUNT), new anchor.BN(TEST_FILE_SIZE));
         await program.methods
           .createDataset(
             contentHash,
@@ -684,56 +1054,43 @@ describe("Africa Research Base (ARB)", () => {
             dataUri,
             new anchor.BN(TEST_COLUMN_COUNT),
             new anchor.BN(TEST_ROW_COUNT),
-            TEST_QUALITY_SCORE
+            TEST_QUALITY_SCORE,
+            attestation.nonce
           )
           .accounts({
-            admin: admin,
             user: researcher1.publicKey,
             contributor: researcher1.publicKey,
             dataset: datasetPda,
+            hashIndex: hashIndexPda(contentHash),
             registry: datasetRegistry,
+            config: configPda,
             reputation: repPda,
             systemProgram: SystemProgram.programId,
+            ...attestation.accounts,
           })
+          .preInstructions([attestation.instruction])
           .signers([researcher1])
           .rpc();
 
         const initialRep = await program.account.reputation.fetch(repPda);
-        const initialScore = initialRep.reputationScore;
+        const initialScore = initialRep.reputationScore.toNumber();
         const initialDownloads = initialRep.totalDownloads.toNumber();
 
-        await program.methods
-          .updateReputationDownload()
-          .accounts({
-            admin: admin,
-            contributor: researcher1.publicKey,
-            reputation: repPda,
-            dataset: datasetPda,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([researcher1])
-          .rpc();
+        await recordDownload(datasetPda, repPda, researcher2);
 
         const updatedRep = await program.account.reputation.fetch(repPda);
-        expect(updatedRep.reputationScore).to.be.greaterThan(initialScore);
+        expect(updatedRep.reputationScore.toNumber()).to.be.greaterThan(initialScore);
         expect(updatedRep.totalDownloads.toNumber()).to.equal(initialDownloads + 1);
       });
 
-      it("Should handle multiple downloads correctly", async () => {
+      it("Should credit each downloader once", async () => {
         const contentHash = generateContentHash("multiple_download_" + Date.now());
         const dataUri = createDataUri("https://drive.google.com/file/d/multiple/view");
         const fileNameBuffer = Buffer.from("multiple.csv", 'utf-8');
 
-        const [datasetPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("dataset"), researcher2.publicKey.toBuffer()],
-          program.programId
-        );
-
-        const [repPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("reputation"), researcher2.publicKey.toBuffer()],
-          program.programId
-        );
+        const [datasetPda, repPda] = await nextDatasetPda(researcher2);
 
+        const attestation = attest(researcher2.publicKey, contentHash, TEST_QUALITY_SCORE, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(TEST_COLUMN_COUNT), new anchor.BN(TEST_FILE_SIZE));
         await program.methods
           .createDataset(
             contentHash,
@@ -743,39 +1100,40 @@ describe("Africa Research Base (ARB)", () => {
             dataUri,
             new anchor.BN(TEST_COLUMN_COUNT),
             new anchor.BN(TEST_ROW_COUNT),
-            TEST_QUALITY_SCORE
+            TEST_QUALITY_SCORE,
+            attestation.nonce
           )
           .accounts({
-            admin: admin,
             user: researcher2.publicKey,
             contributor: researcher2.publicKey,
             dataset: datasetPda,
+            hashIndex: hashIndexPda(contentHash),
             registry: datasetRegistry,
+            config: configPda,
             reputation: repPda,
             systemProgram: SystemProgram.programId,
+            ...attestation.accounts,
           })
+          .preInstructions([attestation.instruction])
           .signers([researcher2])
           .rpc();
 
         const initialRep = await program.account.reputation.fetch(repPda);
         const initialDownloads = initialRep.totalDownloads.toNumber();
 
-        for (let i = 0; i < 3; i++) {
-          await program.methods
-            .updateReputationDownload()
-            .accounts({
-              admin: admin,
-              contributor: researcher2.publicKey,
-              reputation: repPda,
-              dataset: datasetPda,
-              systemProgram: SystemProgram.programId,
-            })
-            .signers([researcher2])
-            .rpc();
+        for (const downloader of [researcher1, researcher3]) {
+          await recordDownload(datasetPda, repPda, downloader);
+        }
+
+        try {
+          await recordDownload(datasetPda, repPda, researcher1);
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.toString()).to.include("already in use");
         }
 
         const finalRep = await program.account.reputation.fetch(repPda);
-        expect(finalRep.totalDownloads.toNumber()).to.equal(initialDownloads + 3);
+        expect(finalRep.totalDownloads.toNumber()).to.equal(initialDownloads + 2);
       });
     });
 
@@ -785,17 +1143,9 @@ describe("Africa Research Base (ARB)", () => {
         const dataUri = createDataUri("https://drive.google.com/file/d/citation/view");
         const fileNameBuffer = Buffer.from("citation_test.csv", 'utf-8');
 
-        // FIXED: Include content hash consistently
-        const [datasetPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("dataset"), researcher3.publicKey.toBuffer(), Buffer.from(contentHash)],
-          program.programId
-        );
-
-        const [repPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("reputation"), researcher3.publicKey.toBuffer()],
-          program.programId
-        );
+        const [datasetPda, repPda] = await nextDatasetPda(researcher3);
 
+        const attestation = attest(researcher3.publicKey, contentHash, TEST_QUALITY_SCORE, new anchor.BN(TEST_ROW_COUNT), new anchor.BN(TEST_COLUMN_COUNT), new anchor.BN(TEST_FILE_SIZE));
         await program.methods
           .createDataset(
             contentHash,
@@ -805,32 +1155,57 @@ describe("Africa Research Base (ARB)", () => {
             dataUri,
             new anchor.BN(TEST_COLUMN_COUNT),
             new anchor.BN(TEST_ROW_COUNT),
-            TEST_QUALITY_SCORE
+            TEST_QUALITY_SCORE,
+            attestation.nonce
           )
           .accounts({
-            admin: admin,
             user: researcher3.publicKey,
             contributor: researcher3.publicKey,
             dataset: datasetPda,
+            hashIndex: hashIndexPda(contentHash),
             registry: datasetRegistry,
+            config: configPda,
             reputation: repPda,
             systemProgram: SystemProgram.programId,
+            ...attestation.accounts,
           })
+          .preInstructions([attestation.instruction])
           .signers([researcher3])
           .rpc();
 
         const initialRep = await program.account.reputation.fetch(repPda);
-        const initialScore = initialRep.reputationScore;
+        const initialScore = initialRep.reputationScore.toNumber();
 
+        // Citations are recorded by the citing wallet, once per publication
+        const doi = "10.1000/arb.citation.test";
+        const publicationHash = Array.from(Buffer.from(
+          sha256(Buffer.concat([Buffer.from([0]), Buffer.from(doi, 'utf-8')])), 'hex'
+        ));
         await program.methods
-          .updateReputationCitation()
+          .recordCitation(publicationHash, {
+            kind: { doi: {} },
+            identifier: doi,
+            titleHash: generateContentHash("citation_test_paper"),
+            year: 2024,
+            venue: "Journal of African Research Data",
+          })
           .accounts({
-            admin: admin,
-            contributor: researcher3.publicKey,
-            reputation: repPda,
+            citer: researcher1.publicKey,
+            config: configPda,
             dataset: datasetPda,
+            citation: PublicKey.findProgramAddressSync(
+              [Buffer.from("citation"), datasetPda.toBuffer(), researcher1.publicKey.toBuffer(), Buffer.from(publicationHash)],
+              program.programId
+            )[0],
+            reputation: repPda,
             systemProgram: SystemProgram.programId,
-          });
+          })
+          .signers([researcher1])
+          .rpc();
+
+        const updatedRep = await program.account.reputation.fetch(repPda);
+        expect(updatedRep.reputationScore.toNumber()).to.be.greaterThan(initialScore);
+        expect(updatedRep.totalCitations).to.equal(initialRep.totalCitations + 1);
       });
     });
   });
@@ -0,0 +1,380 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { PublicKey, Keypair, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { AfricaResearchBase } from '../target/types/africa_research_base';
+import {
+  createDataUri,
+  deriveContentHashIndexPDA,
+  ensureRegistryAndConfig,
+  generateContentHash,
+  signQualityAttestation,
+} from './utils';
+
+describe("Reputation authority and roles", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.AfricaResearchBase as Program<AfricaResearchBase>;
+
+  const admin = provider.wallet.publicKey;
+  const researcher = Keypair.generate();
+  const attacker = Keypair.generate();
+  const qualityOracle = Keypair.generate();
+
+  let registryPda: PublicKey;
+  let configPda: PublicKey;
+  let adminAuthority: PublicKey;
+  let attackerAuthority: PublicKey;
+  let researcherAuthority: PublicKey;
+  let reputationPda: PublicKey;
+  let datasetPda: PublicKey;
+
+  const authorityPda = (holder: PublicKey): PublicKey => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("authority"), registryPda.toBuffer(), holder.toBuffer()],
+      program.programId
+    )[0];
+  };
+
+  const hashIndexPda = (contentHash: number[]): PublicKey => {
+    return deriveContentHashIndexPDA(contentHash, program.programId)[0];
+  };
+
+  const grantRole = async (holder: PublicKey, role: any) => {
+    await program.methods
+      .grantRole(role)
+      .accounts({
+        admin: admin,
+        registry: registryPda,
+        adminAuthority: null,
+        holder: holder,
+        authority: authorityPda(holder),
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  };
+
+  const expectMissingRole = (error: any) => {
+    const errorMessage = error.toString();
+    const hasExpectedError = errorMessage.includes("MissingRole") ||
+                            errorMessage.includes("Signer does not hold the required role") ||
+                            errorMessage.includes("0x177f"); // Error code 6015
+    expect(hasExpectedError).to.be.true;
+  };
+
+  const expectUnauthorized = (error: any) => {
+    const errorMessage = error.toString();
+    const hasExpectedError = errorMessage.includes("UnauthorizedReputationUpdate") ||
+                            errorMessage.includes("Unauthorized reputation update") ||
+                            errorMessage.includes("0x177c"); // Error code 6012
+    expect(hasExpectedError).to.be.true;
+  };
+
+  let attestationNonce = 0;
+  const attest = (contributor: PublicKey, contentHash: number[], qualityScore: number, rowCount: anchor.BN, columnCount: anchor.BN, fileSize: anchor.BN) => {
+    return signQualityAttestation(
+      qualityOracle, registryPda, program.programId,
+      contributor, contentHash, qualityScore, rowCount, columnCount, fileSize, new anchor.BN(attestationNonce++)
+    );
+  };
+
+  before(async () => {
+    for (const keypair of [researcher, attacker]) {
+      const signature = await provider.connection.requestAirdrop(
+        keypair.publicKey,
+        2 * anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(signature);
+    }
+
+    ({ registry: registryPda, config: configPda } = await ensureRegistryAndConfig(program, provider));
+    [reputationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation"), researcher.publicKey.toBuffer()],
+      program.programId
+    );
+
+    adminAuthority = authorityPda(admin);
+    attackerAuthority = authorityPda(attacker.publicKey);
+    researcherAuthority = authorityPda(researcher.publicKey);
+
+    // The attacker holds a role, just not the one reputation updates require
+    await grantRole(admin, { reputationOracle: {} });
+    await grantRole(attacker.publicKey, { moderator: {} });
+    await grantRole(researcher.publicKey, { reviewer: {} });
+    await grantRole(qualityOracle.publicKey, { qualityOracle: {} });
+
+    await program.methods
+      .initializeReputation()
+      .accounts({
+        user: researcher.publicKey,
+        contributor: researcher.publicKey,
+        reputation: reputationPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([researcher])
+      .rpc();
+
+    const datasetIndex = Buffer.alloc(4);
+    datasetIndex.writeUInt32LE(0);
+    [datasetPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dataset"), researcher.publicKey.toBuffer(), datasetIndex],
+      program.programId
+    );
+
+    const contentHash = generateContentHash("authority_test_" + Date.now());
+    const attestation = attest(researcher.publicKey, contentHash, 80, new anchor.BN(10), new anchor.BN(4), new anchor.BN(1024));
+    await program.methods
+      .createDataset(
+        contentHash,
+        Buffer.from('{"authority": true}', 'utf-8'),
+        Buffer.from("authority.csv", 'utf-8'),
+        new anchor.BN(1024),
+        createDataUri("https://drive.google.com/file/d/authority/view"),
+        new anchor.BN(4),
+        new anchor.BN(10),
+        80,
+        attestation.nonce
+      )
+      .accounts({
+        user: researcher.publicKey,
+        contributor: researcher.publicKey,
+        registry: registryPda,
+        config: configPda,
+        dataset: datasetPda,
+        hashIndex: hashIndexPda(contentHash),
+        reputation: reputationPda,
+        systemProgram: SystemProgram.programId,
+        ...attestation.accounts,
+      })
+      .preInstructions([attestation.instruction])
+      .signers([researcher])
+      .rpc();
+  });
+
+  describe("Arbitrary signer is rejected", () => {
+    it("Should reject update_reputation_upload", async () => {
+      try {
+        await program.methods
+          .updateReputationUpload()
+          .accounts({
+            oracle: attacker.publicKey,
+            registry: registryPda,
+            oracleAuthority: attackerAuthority,
+            config: configPda,
+            contributor: researcher.publicKey,
+            reputation: reputationPda,
+            dataset: datasetPda,
+          })
+          .signers([attacker])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expectUnauthorized(error);
+      }
+    });
+
+    it("Should reject update_reputation_review", async () => {
+      try {
+        await program.methods
+          .updateReputationReview()
+          .accounts({
+            oracle: attacker.publicKey,
+            registry: registryPda,
+            oracleAuthority: attackerAuthority,
+            config: configPda,
+            contributor: researcher.publicKey,
+            reputation: reputationPda,
+            reviewerAuthority: researcherAuthority,
+            review: PublicKey.findProgramAddressSync(
+              [Buffer.from("review"), datasetPda.toBuffer(), researcher.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            reviewerStake: PublicKey.findProgramAddressSync(
+              [Buffer.from("review_stake"), researcher.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([attacker, researcher])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expectUnauthorized(error);
+      }
+    });
+
+    it("Should reject update_reputation_activity", async () => {
+      try {
+        await program.methods
+          .updateReputationActivity()
+          .accounts({
+            oracle: attacker.publicKey,
+            registry: registryPda,
+            oracleAuthority: attackerAuthority,
+            config: configPda,
+            contributor: researcher.publicKey,
+            reputation: reputationPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([attacker, researcher])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expectUnauthorized(error);
+      }
+    });
+
+    it("Should reject an attacker signing against their own registry", async () => {
+      // The pre-singleton seeds let any wallet derive a registry it controls
+      const [attackerRegistry] = PublicKey.findProgramAddressSync(
+        [Buffer.from("registry"), attacker.publicKey.toBuffer()],
+        program.programId
+      );
+      const [attackerRegistryAuthority] = PublicKey.findProgramAddressSync(
+        [Buffer.from("authority"), attackerRegistry.toBuffer(), attacker.publicKey.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .updateReputationActivity()
+          .accounts({
+            oracle: attacker.publicKey,
+            registry: attackerRegistry,
+            oracleAuthority: attackerRegistryAuthority,
+            config: configPda,
+            contributor: researcher.publicKey,
+            reputation: reputationPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([attacker, researcher])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        const errorMessage = error.toString();
+        const hasExpectedError = errorMessage.includes("ConstraintSeeds") ||
+                                errorMessage.includes("AccountNotInitialized");
+        expect(hasExpectedError).to.be.true;
+      }
+    });
+
+    it("Should leave the reputation account untouched", async () => {
+      const reputationAccount = await program.account.reputation.fetch(reputationPda);
+      const datasetAccount = await program.account.dataset.fetch(datasetPda);
+      expect(reputationAccount.totalUploadPoints.toNumber()).to.equal(datasetAccount.uploadPoints);
+      expect(reputationAccount.totalReviewPoints.toNumber()).to.equal(0);
+      expect(reputationAccount.totalActivityPoints.toNumber()).to.equal(0);
+      expect(reputationAccount.totalDownloads.toNumber()).to.equal(0);
+      expect(reputationAccount.totalCitations).to.equal(0);
+    });
+  });
+
+  describe("Reputation oracle is accepted", () => {
+    it("Should re-score upload points when signed by a reputation oracle", async () => {
+      await program.methods
+        .updateReputationUpload()
+        .accounts({
+          oracle: admin,
+          registry: registryPda,
+          oracleAuthority: adminAuthority,
+          config: configPda,
+          contributor: researcher.publicKey,
+          reputation: reputationPda,
+          dataset: datasetPda,
+        })
+        .rpc();
+
+      // Re-scoring under an unchanged config must not credit the upload twice
+      const reputationAccount = await program.account.reputation.fetch(reputationPda);
+      const datasetAccount = await program.account.dataset.fetch(datasetPda);
+      expect(datasetAccount.uploadPoints).to.equal(300);
+      expect(reputationAccount.totalUploadPoints.toNumber()).to.equal(300);
+    });
+  });
+
+  describe("Role management", () => {
+    it("Should reject grant_role from a signer without SuperAdmin", async () => {
+      try {
+        await program.methods
+          .grantRole({ reputationOracle: {} })
+          .accounts({
+            admin: attacker.publicKey,
+            registry: registryPda,
+            adminAuthority: attackerAuthority,
+            holder: attacker.publicKey,
+            authority: attackerAuthority,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([attacker])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expectMissingRole(error);
+      }
+    });
+
+    it("Should revoke a granted role", async () => {
+      await program.methods
+        .revokeRole({ reviewer: {} })
+        .accounts({
+          admin: admin,
+          registry: registryPda,
+          adminAuthority: null,
+          authority: researcherAuthority,
+        })
+        .rpc();
+
+      const authorityAccount = await program.account.authority.fetch(researcherAuthority);
+      expect(authorityAccount.roles).to.equal(0);
+    });
+  });
+
+  describe("Circuit breaker", () => {
+    const setPause = async (method: "pause" | "unpause", category: any) => {
+      await program.methods[method](category)
+        .accounts({
+          admin: admin,
+          registry: registryPda,
+          adminAuthority: null,
+          config: configPda,
+        })
+        .rpc();
+    };
+
+    it("Should reject reputation updates while the category is paused", async () => {
+      await setPause("pause", { reputation: {} });
+
+      try {
+        await program.methods
+          .updateReputationUpload()
+          .accounts({
+            oracle: admin,
+            registry: registryPda,
+            oracleAuthority: adminAuthority,
+            config: configPda,
+            contributor: researcher.publicKey,
+            reputation: reputationPda,
+            dataset: datasetPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("ProgramPaused");
+      } finally {
+        await setPause("unpause", { reputation: {} });
+      }
+    });
+
+    it("Should toggle the global pause flag", async () => {
+      await setPause("pause", null);
+      let configAccount = await program.account.programConfig.fetch(configPda);
+      expect(configAccount.paused).to.equal(true);
+
+      await setPause("unpause", null);
+      configAccount = await program.account.programConfig.fetch(configPda);
+      expect(configAccount.paused).to.equal(false);
+      expect(configAccount.pausedCategories).to.equal(0);
+    });
+  });
+});
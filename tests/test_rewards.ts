@@ -0,0 +1,617 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { PublicKey, Keypair, SystemProgram } from '@solana/web3.js';
+import {
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from '@solana/spl-token';
+import { expect } from 'chai';
+import { AfricaResearchBase } from '../target/types/africa_research_base';
+import { ensureArbMint, ensureRegistryAndConfig } from './utils';
+
+describe("Reward vault and redemption", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.AfricaResearchBase as Program<AfricaResearchBase>;
+
+  const admin = provider.wallet.publicKey;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const researcher = Keypair.generate();
+  const bystander = Keypair.generate();
+  const treasurer = Keypair.generate();
+
+  let registryPda: PublicKey;
+  let configPda: PublicKey;
+  let reputationPda: PublicKey;
+  let arbMint: PublicKey;
+  let rewardVault: PublicKey;
+
+  const [vaultAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_authority")],
+    program.programId
+  );
+
+  const authorityPda = (holder: PublicKey): PublicKey => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("authority"), registryPda.toBuffer(), holder.toBuffer()],
+      program.programId
+    )[0];
+  };
+
+  // Without a signer the registry admin sets the vault up as SuperAdmin
+  const initializeRewardVault = async (signer?: Keypair) => {
+    await program.methods
+      .initializeRewardVault()
+      .accounts({
+        admin: signer ? signer.publicKey : admin,
+        registry: registryPda,
+        adminAuthority: signer ? authorityPda(signer.publicKey) : null,
+        config: configPda,
+        arbMint: arbMint,
+        vaultAuthority: vaultAuthority,
+        rewardVault: rewardVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers(signer ? [signer] : [])
+      .rpc();
+  };
+
+  const grantRole = async (role: Record<string, object>, holder: PublicKey) => {
+    await program.methods
+      .grantRole(role)
+      .accounts({
+        admin: admin,
+        registry: registryPda,
+        adminAuthority: null,
+        holder: holder,
+        authority: authorityPda(holder),
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  };
+
+  const updateScoring = async (changes: Record<string, unknown>) => {
+    const configAccount = await program.account.programConfig.fetch(configPda);
+    await program.methods
+      .updateConfig({ ...configAccount.scoring, ...changes })
+      .accounts({
+        admin: admin,
+        registry: registryPda,
+        adminAuthority: null,
+        config: configPda,
+      })
+      .rpc();
+  };
+
+  const redeem = async (points: number, overrides: Record<string, PublicKey> = {}) => {
+    await program.methods
+      .redeemPoints(new anchor.BN(points))
+      .accounts({
+        user: researcher.publicKey,
+        reputation: reputationPda,
+        config: configPda,
+        arbMint: arbMint,
+        rewardVault: rewardVault,
+        userTokenAccount: getAssociatedTokenAddressSync(arbMint, researcher.publicKey),
+        vaultAuthority: vaultAuthority,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        ...overrides,
+      })
+      .signers([researcher])
+      .rpc();
+  };
+
+  before(async () => {
+    for (const keypair of [researcher, bystander, treasurer]) {
+      const signature = await provider.connection.requestAirdrop(
+        keypair.publicKey,
+        2 * anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(signature);
+    }
+
+    ({ registry: registryPda, config: configPda } = await ensureRegistryAndConfig(program, provider));
+    [reputationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation"), researcher.publicKey.toBuffer()],
+      program.programId
+    );
+
+    arbMint = await ensureArbMint(program, provider);
+    rewardVault = getAssociatedTokenAddressSync(arbMint, vaultAuthority, true);
+
+    await program.methods
+      .initializeReputation()
+      .accounts({
+        user: researcher.publicKey,
+        contributor: researcher.publicKey,
+        reputation: reputationPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([researcher])
+      .rpc();
+
+    // Earn some points through the activity reward
+    await grantRole({ reputationOracle: {} }, admin);
+    await program.methods
+      .updateReputationActivity()
+      .accounts({
+        oracle: admin,
+        registry: registryPda,
+        oracleAuthority: authorityPda(admin),
+        config: configPda,
+        contributor: researcher.publicKey,
+        reputation: reputationPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([researcher])
+      .rpc();
+  });
+
+  describe("Reward Vault", () => {
+    it("Should reject vault setup by a wallet without the Treasurer role", async () => {
+      await grantRole({ reviewer: {} }, treasurer.publicKey);
+      try {
+        await initializeRewardVault(treasurer);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("MissingRole");
+      }
+    });
+
+    it("Should let a Treasurer create the vault and record it in the config", async () => {
+      await grantRole({ treasurer: {} }, treasurer.publicKey);
+      await initializeRewardVault(treasurer);
+
+      const configAccount = await program.account.programConfig.fetch(configPda);
+      expect(configAccount.rewardVault.toString()).to.equal(rewardVault.toString());
+      expect(configAccount.arbMint.toString()).to.equal(arbMint.toString());
+    });
+
+    it("Should reject initializing the vault twice", async () => {
+      try {
+        await initializeRewardVault();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("RewardVaultAlreadyInitialized");
+      }
+    });
+
+    it("Should accept funding from any ARB holder", async () => {
+      const adminTokenAccount = (await getOrCreateAssociatedTokenAccount(
+        provider.connection, payer, arbMint, admin
+      )).address;
+      await mintTo(provider.connection, payer, arbMint, adminTokenAccount, payer, 1_000_000_000_000);
+
+      const before = await getAccount(provider.connection, rewardVault);
+      await program.methods
+        .fundRewardVault(new anchor.BN(500_000_000_000))
+        .accounts({
+          funder: admin,
+          config: configPda,
+          rewardVault: rewardVault,
+          funderTokenAccount: adminTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      const after = await getAccount(provider.connection, rewardVault);
+      expect(Number(after.amount) - Number(before.amount)).to.equal(500_000_000_000);
+    });
+  });
+
+  describe("Conversion Rate", () => {
+    it("Should reject a zero rate denominator", async () => {
+      const configAccount = await program.account.programConfig.fetch(configPda);
+      try {
+        await program.methods
+          .updateConfig({ ...configAccount.scoring, pointsToArbDenominator: new anchor.BN(0) })
+          .accounts({
+            admin: admin,
+            registry: registryPda,
+            adminAuthority: null,
+            config: configPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidConfig");
+      }
+    });
+  });
+
+  describe("Redemption", () => {
+    it("Should reject a vault other than the configured one", async () => {
+      const adminTokenAccount = getAssociatedTokenAddressSync(arbMint, admin);
+      try {
+        await redeem(10, { rewardVault: adminTokenAccount });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidRewardVault");
+      }
+    });
+
+    it("Should reject a mint other than the configured ARB mint", async () => {
+      const otherMint = await createMint(provider.connection, payer, payer.publicKey, null, 9);
+      try {
+        await redeem(10, {
+          arbMint: otherMint,
+          userTokenAccount: getAssociatedTokenAddressSync(otherMint, researcher.publicKey),
+        });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidMint");
+      }
+    });
+
+    it("Should reject paying out to another wallet's token account", async () => {
+      const bystanderTokenAccount = (await getOrCreateAssociatedTokenAccount(
+        provider.connection, payer, arbMint, bystander.publicKey
+      )).address;
+      try {
+        await redeem(10, { userTokenAccount: bystanderTokenAccount });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("ConstraintTokenOwner");
+      }
+    });
+
+    it("Should reject a claim below the configured minimum", async () => {
+      const { scoring } = await program.account.programConfig.fetch(configPda);
+      try {
+        await redeem(scoring.minRedeemPoints.toNumber() - 1);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("RedeemBelowMinimum");
+      }
+    });
+
+    it("Should reject a claim larger than the available points", async () => {
+      const reputation = await program.account.reputation.fetch(reputationPda);
+      try {
+        await redeem(reputation.redeemablePoints.toNumber() + 1);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("RedeemExceedsBalance");
+      }
+    });
+
+    it("Should create the user's token account and pay a partial claim", async () => {
+      const researcherTokenAccount = getAssociatedTokenAddressSync(arbMint, researcher.publicKey);
+      expect(await provider.connection.getAccountInfo(researcherTokenAccount)).to.be.null;
+
+      const { scoring } = await program.account.programConfig.fetch(configPda);
+      const points = scoring.minRedeemPoints.toNumber();
+      const before = await program.account.reputation.fetch(reputationPda);
+
+      let redeemed = null;
+      const listener = program.addEventListener("pointsRedeemed", (event) => {
+        redeemed = event;
+      });
+      await redeem(points);
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+
+      const reputation = await program.account.reputation.fetch(reputationPda);
+      expect(reputation.claimedPoints.toNumber()).to.equal(points);
+      expect(reputation.redeemablePoints.toNumber())
+        .to.equal(before.redeemablePoints.toNumber() - points);
+      // Redeeming spends the balance, not the standing
+      expect(reputation.reputationScore.toNumber()).to.equal(before.reputationScore.toNumber());
+
+      // Whole ARB per point, scaled by the mint's 9 decimals
+      const expected = points * scoring.pointsToArbNumerator.toNumber() * 1e9
+        / scoring.pointsToArbDenominator.toNumber();
+      const tokenAccount = await getAccount(provider.connection, researcherTokenAccount);
+      expect(Number(tokenAccount.amount)).to.equal(expected);
+
+      expect(redeemed).to.not.be.null;
+      expect(redeemed.points.toNumber()).to.equal(points);
+      expect(redeemed.tokenAmount.toNumber()).to.equal(expected);
+      expect(redeemed.remainingPoints.toNumber()).to.equal(before.redeemablePoints.toNumber() - points);
+    });
+
+    it("Should reject redeeming once everything has been claimed", async () => {
+      const reputation = await program.account.reputation.fetch(reputationPda);
+      const remaining = reputation.redeemablePoints.toNumber();
+      if (remaining > 0) {
+        await redeem(remaining);
+      }
+
+      try {
+        await redeem(10);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("NothingToRedeem");
+      }
+    });
+  });
+
+  describe("Account Migration", () => {
+    it("Should create accounts on the current layout version", async () => {
+      const registry = await program.account.registry.fetch(registryPda);
+      const reputation = await program.account.reputation.fetch(reputationPda);
+      expect(registry.accountVersion).to.equal(1);
+      expect(reputation.accountVersion).to.equal(1);
+      expect(reputation.tier).to.deep.equal({ novice: {} });
+    });
+
+    it("Should reject migrating an account as the wrong type", async () => {
+      try {
+        await program.methods
+          .migrateDataset()
+          .accounts({
+            payer: admin,
+            config: configPda,
+            dataset: reputationPda,
+            reputation: reputationPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UnsupportedAccountLayout");
+      }
+    });
+
+    it("Should reject migrating a reputation already on the current layout", async () => {
+      try {
+        await program.methods
+          .migrateReputation()
+          .accounts({
+            payer: admin,
+            config: configPda,
+            contributor: researcher.publicKey,
+            reputation: reputationPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("AccountAlreadyMigrated");
+      }
+    });
+  });
+
+  describe("Reputation Decay", () => {
+    it("Should reject a decay period shorter than a day", async () => {
+      try {
+        await updateScoring({ decayEnabled: true, decayPeriod: new anchor.BN(3600) });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidConfig");
+      }
+    });
+
+    it("Should let any payer refresh a standing without touching the redeemable balance", async () => {
+      await updateScoring({ decayEnabled: true });
+      try {
+        const before = await program.account.reputation.fetch(reputationPda);
+        await program.methods
+          .refreshReputation()
+          .accounts({
+            config: configPda,
+            reputation: reputationPda,
+          })
+          .rpc();
+
+        // Active within the decay period, so nothing has decayed yet
+        const after = await program.account.reputation.fetch(reputationPda);
+        expect(after.reputationScore.toNumber()).to.equal(before.earnedPoints.toNumber());
+        expect(after.earnedPoints.toNumber()).to.equal(before.earnedPoints.toNumber());
+        expect(after.redeemablePoints.toNumber()).to.equal(before.redeemablePoints.toNumber());
+      } finally {
+        await updateScoring({ decayEnabled: false });
+      }
+    });
+
+    it("Should not reprice earned points when the weights change", async () => {
+      const scoring = (await program.account.programConfig.fetch(configPda)).scoring;
+      await updateScoring({
+        downloadWeight: scoring.downloadWeight * 2,
+        citationWeight: scoring.citationWeight * 2,
+      });
+      try {
+        const before = await program.account.reputation.fetch(reputationPda);
+        await program.methods
+          .refreshReputation()
+          .accounts({
+            config: configPda,
+            reputation: reputationPda,
+          })
+          .rpc();
+
+        const after = await program.account.reputation.fetch(reputationPda);
+        expect(after.earnedPoints.toNumber()).to.equal(before.earnedPoints.toNumber());
+        expect(after.redeemablePoints.toNumber()).to.equal(before.redeemablePoints.toNumber());
+      } finally {
+        await updateScoring({
+          downloadWeight: scoring.downloadWeight,
+          citationWeight: scoring.citationWeight,
+        });
+      }
+    });
+  });
+
+  describe("Vesting", () => {
+    const [bystanderReputation] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation"), bystander.publicKey.toBuffer()],
+      program.programId
+    );
+    // One schedule per vested redemption, indexed by the reputation's `vestingCount`
+    const vestingSchedule = (index: number): PublicKey => {
+      const indexBuffer = Buffer.alloc(4);
+      indexBuffer.writeUInt32LE(index);
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("vesting"), bystander.publicKey.toBuffer(), indexBuffer],
+        program.programId
+      )[0];
+    };
+    const [vestingEscrow] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vesting_escrow"), bystander.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const redeemVested = async (points: number): Promise<PublicKey> => {
+      const reputation = await program.account.reputation.fetch(bystanderReputation);
+      const schedule = vestingSchedule(reputation.vestingCount);
+      await program.methods
+        .redeemPointsVested(new anchor.BN(points))
+        .accounts({
+          user: bystander.publicKey,
+          reputation: bystanderReputation,
+          config: configPda,
+          arbMint: arbMint,
+          rewardVault: rewardVault,
+          vaultAuthority: vaultAuthority,
+          vestingSchedule: schedule,
+          vestingEscrow: vestingEscrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bystander])
+        .rpc();
+      return schedule;
+    };
+
+    const claimVested = async (schedule: PublicKey) => {
+      await program.methods
+        .claimVested()
+        .accounts({
+          user: bystander.publicKey,
+          config: configPda,
+          arbMint: arbMint,
+          vestingSchedule: schedule,
+          vestingEscrow: vestingEscrow,
+          userTokenAccount: getAssociatedTokenAddressSync(arbMint, bystander.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bystander])
+        .rpc();
+    };
+
+    let lockedSchedule: PublicKey;
+
+    before(async () => {
+      await program.methods
+        .initializeReputation()
+        .accounts({
+          user: bystander.publicKey,
+          contributor: bystander.publicKey,
+          reputation: bystanderReputation,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bystander])
+        .rpc();
+      await program.methods
+        .updateReputationActivity()
+        .accounts({
+          oracle: admin,
+          registry: registryPda,
+          oracleAuthority: authorityPda(admin),
+          config: configPda,
+          contributor: bystander.publicKey,
+          reputation: bystanderReputation,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bystander])
+        .rpc();
+    });
+
+    after(async () => {
+      // Other suites share the config and expect immediate redemption
+      await updateScoring({ vestingEnabled: false });
+    });
+
+    it("Should reject vested redemption while vesting is disabled", async () => {
+      try {
+        await redeemVested(10);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("VestingDisabled");
+      }
+    });
+
+    it("Should reject a cliff longer than the vesting duration", async () => {
+      try {
+        await updateScoring({
+          vestingEnabled: true,
+          vestingCliff: new anchor.BN(7200),
+          vestingDuration: new anchor.BN(3600),
+        });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidConfig");
+      }
+    });
+
+    it("Should escrow redeemed tokens and lock them until the cliff", async () => {
+      await updateScoring({
+        vestingEnabled: true,
+        vestingCliff: new anchor.BN(3600),
+        vestingDuration: new anchor.BN(7200),
+      });
+
+      try {
+        await redeem(10);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("VestingRequired");
+      }
+
+      lockedSchedule = await redeemVested(10);
+
+      const schedule = await program.account.vestingSchedule.fetch(lockedSchedule);
+      const escrow = await getAccount(provider.connection, vestingEscrow);
+      expect(schedule.beneficiary.toString()).to.equal(bystander.publicKey.toString());
+      expect(schedule.index).to.equal(0);
+      expect(schedule.totalAmount.toNumber()).to.equal(10 * 1e9);
+      expect(schedule.cliffTime.toNumber() - schedule.startTime.toNumber()).to.equal(3600);
+      expect(Number(escrow.amount)).to.equal(10 * 1e9);
+
+      try {
+        await claimVested(lockedSchedule);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("NothingVested");
+      }
+    });
+
+    it("Should vest a later redemption on its own schedule", async () => {
+      await updateScoring({
+        vestingCliff: new anchor.BN(0),
+        vestingDuration: new anchor.BN(1),
+      });
+      const laterSchedule = await redeemVested(10);
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+
+      // The earlier redemption keeps its own cliff and stays locked
+      const earlier = await program.account.vestingSchedule.fetch(lockedSchedule);
+      expect(earlier.totalAmount.toNumber()).to.equal(10 * 1e9);
+      expect(earlier.cliffTime.toNumber() - earlier.startTime.toNumber()).to.equal(3600);
+
+      await claimVested(laterSchedule);
+
+      // Fully claimed schedules are closed
+      expect(await provider.connection.getAccountInfo(laterSchedule)).to.equal(null);
+      const escrow = await getAccount(provider.connection, vestingEscrow);
+      const wallet = await getAccount(
+        provider.connection,
+        getAssociatedTokenAddressSync(arbMint, bystander.publicKey)
+      );
+      expect(Number(escrow.amount)).to.equal(10 * 1e9);
+      expect(Number(wallet.amount)).to.equal(10 * 1e9);
+    });
+  });
+});
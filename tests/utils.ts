@@ -1,10 +1,25 @@
 import * as anchor from '@coral-xyz/anchor';
-import { BN } from '@coral-xyz/anchor';
+import { BN, Program } from '@coral-xyz/anchor';
+import { createMint, TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { sha256 } from 'js-sha256';
 
 export const PROGRAM_SEED = 'africa_research_base';
 export const REGISTRY_SEED = 'registry';
 export const DATASET_SEED = 'dataset';
 export const REPUTATION_SEED = 'reputation';
+export const CONTENT_HASH_SEED = 'hash';
+export const ATTESTATION_NONCE_SEED = 'nonce';
+
+export const generateContentHash = (data: string): number[] => {
+  return Array.from(Buffer.from(sha256(data), 'hex'));
+};
+
+// data_uri is a fixed 256-byte field, zero-padded
+export const createDataUri = (uri: string): number[] => {
+  const paddedBuffer = Buffer.alloc(256);
+  Buffer.from(uri, 'utf-8').copy(paddedBuffer);
+  return Array.from(paddedBuffer);
+};
 
 export const findProgramAddress = async (
   seeds: Array<Buffer | Uint8Array>,
@@ -14,26 +29,31 @@ export const findProgramAddress = async (
 };
 
 export const deriveRegistryPDA = async (
-  admin: anchor.web3.PublicKey,
   programId: anchor.web3.PublicKey
 ): Promise<[anchor.web3.PublicKey, number]> => {
-  return await findProgramAddress(
-    [Buffer.from(REGISTRY_SEED), admin.toBuffer()],
-    programId
-  );
+  return await findProgramAddress([Buffer.from(REGISTRY_SEED)], programId);
+};
+
+// The upgradeable loader's ProgramData account; its upgrade authority may initialize the registry
+export const deriveProgramDataAddress = (
+  programId: anchor.web3.PublicKey
+): anchor.web3.PublicKey => {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [programId.toBuffer()],
+    new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  )[0];
 };
 
+// Datasets are seeded by the contributor's dataset_index, a u32 in little-endian order
 export const deriveDatasetPDA = async (
   contributor: anchor.web3.PublicKey,
-  //contentHash: number[],
+  datasetIndex: number,
   programId: anchor.web3.PublicKey
 ): Promise<[anchor.web3.PublicKey, number]> => {
+  const indexBytes = Buffer.alloc(4);
+  indexBytes.writeUInt32LE(datasetIndex);
   return await findProgramAddress(
-    [
-      Buffer.from(DATASET_SEED),
-      contributor.toBuffer(),
-      //Buffer.from(contentHash)
-    ],
+    [Buffer.from(DATASET_SEED), contributor.toBuffer(), indexBytes],
     programId
   );
 };
@@ -48,6 +68,159 @@ export const deriveReputationPDA = async (
   );
 };
 
+// Resolves a file's content hash to the ContentHashIndex that points at its dataset
+export const deriveContentHashIndexPDA = (
+  contentHash: number[],
+  programId: anchor.web3.PublicKey
+): [anchor.web3.PublicKey, number] => {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(CONTENT_HASH_SEED), Buffer.from(contentHash)],
+    programId
+  );
+};
+
+export const deriveAttestationNoncePDA = (
+  oracle: anchor.web3.PublicKey,
+  nonce: BN,
+  programId: anchor.web3.PublicKey
+): [anchor.web3.PublicKey, number] => {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(ATTESTATION_NONCE_SEED), oracle.toBuffer(), nonce.toArrayLike(Buffer, 'le', 8)],
+    programId
+  );
+};
+
+// Matches quality_attestation_message in create_dataset.rs
+export const qualityAttestationMessage = (
+  contributor: anchor.web3.PublicKey,
+  contentHash: number[],
+  qualityScore: number,
+  rowCount: BN,
+  columnCount: BN,
+  fileSize: BN,
+  nonce: BN
+): Buffer => {
+  return Buffer.concat([
+    contributor.toBuffer(),
+    Buffer.from(contentHash),
+    Buffer.from([qualityScore]),
+    rowCount.toArrayLike(Buffer, 'le', 8),
+    columnCount.toArrayLike(Buffer, 'le', 8),
+    fileSize.toArrayLike(Buffer, 'le', 8),
+    nonce.toArrayLike(Buffer, 'le', 8),
+  ]);
+};
+
+// Builds the Ed25519 instruction and accounts create_dataset and update_dataset need to accept an oracle-signed quality score
+export const signQualityAttestation = (
+  oracle: anchor.web3.Keypair,
+  registry: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey,
+  contributor: anchor.web3.PublicKey,
+  contentHash: number[],
+  qualityScore: number,
+  rowCount: BN,
+  columnCount: BN,
+  fileSize: BN,
+  nonce: BN
+) => {
+  const message = qualityAttestationMessage(contributor, contentHash, qualityScore, rowCount, columnCount, fileSize, nonce);
+  const [oracleAuthority] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("authority"), registry.toBuffer(), oracle.publicKey.toBuffer()],
+    programId
+  );
+  const [attestationNonce] = deriveAttestationNoncePDA(oracle.publicKey, nonce, programId);
+
+  return {
+    nonce,
+    instruction: anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: oracle.secretKey,
+      message,
+    }),
+    accounts: {
+      qualityOracle: oracle.publicKey,
+      oracleAuthority,
+      attestationNonce,
+      instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+    },
+  };
+};
+
+// The registry and config are created once by whichever test file runs first. Any other
+// failure is raised rather than skipped.
+export const ensureRegistryAndConfig = async (
+  program: Program<any>,
+  provider: anchor.AnchorProvider
+): Promise<{ registry: anchor.web3.PublicKey; config: anchor.web3.PublicKey }> => {
+  const admin = provider.wallet.publicKey;
+  const [registry] = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from(REGISTRY_SEED)], program.programId);
+  const [config] = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId);
+
+  if (!(await program.account.registry.fetchNullable(registry))) {
+    await program.methods
+      .initializeRegistry()
+      .accounts({
+        admin,
+        user: admin,
+        contributor: admin,
+        registry,
+        program: program.programId,
+        programData: deriveProgramDataAddress(program.programId),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  if (!(await program.account.programConfig.fetchNullable(config))) {
+    await program.methods
+      .initializeConfig()
+      .accounts({
+        admin,
+        registry,
+        config,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  return { registry, config };
+};
+
+// The ARB mint is bound to the config once, so every test file shares the first one created.
+// Its mint authority is always the provider wallet so any file can mint test tokens.
+export const ensureArbMint = async (
+  program: Program<any>,
+  provider: anchor.AnchorProvider
+): Promise<anchor.web3.PublicKey> => {
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const [registry] = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from(REGISTRY_SEED)], program.programId);
+  const [config] = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId);
+
+  const configAccount = await program.account.programConfig.fetch(config);
+  if (!(configAccount.arbMint as anchor.web3.PublicKey).equals(anchor.web3.PublicKey.default)) {
+    return configAccount.arbMint as anchor.web3.PublicKey;
+  }
+
+  const arbMint = await createMint(provider.connection, payer, payer.publicKey, null, 9);
+  await program.methods
+    .initializeReviewStakeVault()
+    .accounts({
+      admin: payer.publicKey,
+      registry,
+      adminAuthority: null,
+      config,
+      arbMint,
+      reviewStakeVault: anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("review_stake_vault")],
+        program.programId
+      )[0],
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .rpc();
+  return arbMint;
+};
+
 export const createBN = (num: number): BN => {
   return new BN(num);
 };
\ No newline at end of file